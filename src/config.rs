@@ -0,0 +1,563 @@
+use serde::Deserialize;
+use std::fmt;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+
+const DEFAULT_CONFIG_PATH: &str = "gpr.toml";
+const DEFAULT_BIND_ADDRESS: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+const DEFAULT_PORT: u16 = 3000;
+const DEFAULT_DATA_DIR: &str = "data";
+const DEFAULT_STATIC_DIR: &str = "static";
+const TRANSMISSIONS_FILE_NAME: &str = "recent_transmissions.json";
+const SQLITE_FILE_NAME: &str = "transmissions.sqlite3";
+const RELEASE_NOTES_FILE_NAME: &str = "Ethereal Waves - Release Notes.md";
+const GRAMMAR_FILE_NAME: &str = "transmission_grammar.toml";
+const DEFAULT_SITE_URL: &str = "http://127.0.0.1:3000";
+const DEFAULT_GENERATION_INTERVAL_SECS: u64 = 3 * 60 * 60;
+const DEFAULT_SHUTDOWN_TIMEOUT_SECS: u64 = 10;
+const MIN_ADMIN_TOKEN_LEN: usize = 16;
+
+/// Each setting as `(toml key, environment variable, command line flag)`.
+const SETTINGS: [(&str, &str, &str); 16] = [
+    ("bind_address", "GPR_BIND_ADDRESS", "--bind-address"),
+    ("port", "GPR_PORT", "--port"),
+    ("data_dir", "GPR_DATA_DIR", "--data-dir"),
+    ("storage", "GPR_STORAGE", "--storage"),
+    ("static_dir", "GPR_STATIC_DIR", "--static-dir"),
+    (
+        "release_notes_path",
+        "GPR_RELEASE_NOTES_PATH",
+        "--release-notes-path",
+    ),
+    (
+        "transmission_grammar_path",
+        "GPR_TRANSMISSION_GRAMMAR_PATH",
+        "--transmission-grammar-path",
+    ),
+    ("site_url", "GPR_SITE_URL", "--site-url"),
+    (
+        "display_timezone",
+        "GPR_DISPLAY_TIMEZONE",
+        "--display-timezone",
+    ),
+    (
+        "generation_interval_secs",
+        "GPR_GENERATION_INTERVAL_SECS",
+        "--generation-interval-secs",
+    ),
+    (
+        "backfill_missed_transmissions",
+        "GPR_BACKFILL_MISSED_TRANSMISSIONS",
+        "--backfill-missed-transmissions",
+    ),
+    (
+        "transmission_seed",
+        "GPR_TRANSMISSION_SEED",
+        "--transmission-seed",
+    ),
+    ("admin_token", "GPR_ADMIN_TOKEN", "--admin-token"),
+    ("log_format", "GPR_LOG_FORMAT", "--log-format"),
+    (
+        "metrics_address",
+        "GPR_METRICS_ADDRESS",
+        "--metrics-address",
+    ),
+    (
+        "shutdown_timeout_secs",
+        "GPR_SHUTDOWN_TIMEOUT_SECS",
+        "--shutdown-timeout-secs",
+    ),
+];
+
+/// Runtime settings resolved from defaults, an optional TOML file,
+/// `GPR_*` environment variables and command line flags, in that order of
+/// precedence.
+#[derive(Clone, Debug)]
+pub(crate) struct Config {
+    pub(crate) bind_address: IpAddr,
+    pub(crate) port: u16,
+    pub(crate) data_dir: PathBuf,
+    pub(crate) storage: StorageBackend,
+    pub(crate) static_dir: PathBuf,
+    pub(crate) release_notes_path: PathBuf,
+    pub(crate) transmission_grammar_path: PathBuf,
+    pub(crate) site_url: String,
+    pub(crate) display_timezone: UtcOffset,
+    pub(crate) generation_interval_secs: u64,
+    /// Generate an entry for every interval missed while the server was
+    /// down instead of a single one.
+    pub(crate) backfill_missed_transmissions: bool,
+    /// Restarts the generator from this seed when it differs from the stored
+    /// one; without it a seed is picked once and kept.
+    pub(crate) transmission_seed: Option<u64>,
+    /// Bearer token for the `/admin` endpoints, which are disabled without it.
+    pub(crate) admin_token: Option<String>,
+    pub(crate) log_format: LogFormat,
+    /// Serves `/metrics` on its own listener instead of alongside the site.
+    pub(crate) metrics_address: Option<SocketAddr>,
+    pub(crate) shutdown_timeout_secs: u64,
+}
+
+/// Where the station log is kept; see `store::open`.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum StorageBackend {
+    /// A JSON file holding only the most recent transmissions.
+    #[default]
+    Json,
+    /// An embedded SQLite database holding the full history.
+    Sqlite,
+}
+
+/// How log lines are written to stdout; see `logging::init`.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum LogFormat {
+    /// `key=value` pairs, one event per line.
+    #[default]
+    Logfmt,
+    /// One JSON object per line.
+    Json,
+}
+
+/// A fixed offset from UTC that times are shown in. Offsets rather than zone
+/// names keep the site free of a timezone database, at the cost of not
+/// following daylight saving time.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(try_from = "String")]
+pub(crate) struct UtcOffset {
+    seconds: i32,
+}
+
+impl UtcOffset {
+    /// Accepts `UTC`, `Z`, or `±HH:MM` / `±HHMM` / `±HH`, optionally after
+    /// `UTC`.
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value.eq_ignore_ascii_case("utc") || value.eq_ignore_ascii_case("z") {
+            return Some(Self::default());
+        }
+
+        let offset = value
+            .strip_prefix("UTC")
+            .or_else(|| value.strip_prefix("utc"))
+            .unwrap_or(value);
+        let (sign, digits) = match offset.split_at_checked(1)? {
+            ("+", digits) => (1, digits),
+            ("-", digits) => (-1, digits),
+            _ => return None,
+        };
+        let digits = digits.replacen(':', "", 1);
+        if !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+        let (hours, minutes) = match digits.len() {
+            2 => (digits.parse::<i32>().ok()?, 0),
+            4 => (digits[..2].parse::<i32>().ok()?, digits[2..].parse().ok()?),
+            _ => return None,
+        };
+        if hours > 14 || minutes > 59 {
+            return None;
+        }
+
+        Some(Self {
+            seconds: sign * (hours * 3_600 + minutes * 60),
+        })
+    }
+
+    pub(crate) fn seconds(self) -> i64 {
+        i64::from(self.seconds)
+    }
+
+    /// The offset as it ends an ISO-8601 timestamp: `Z` or `±HH:MM`.
+    pub(crate) fn iso_suffix(self) -> String {
+        if self.seconds == 0 {
+            return "Z".to_string();
+        }
+
+        let sign = if self.seconds < 0 { '-' } else { '+' };
+        let minutes = self.seconds.unsigned_abs() / 60;
+        format!("{sign}{:02}:{:02}", minutes / 60, minutes % 60)
+    }
+}
+
+impl TryFrom<String> for UtcOffset {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        UtcOffset::parse(&value).ok_or_else(|| {
+            format!("`{value}` is not a timezone (expected UTC or an offset like +02:00)")
+        })
+    }
+}
+
+impl fmt::Display for UtcOffset {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.seconds {
+            0 => formatter.write_str("UTC"),
+            _ => write!(formatter, "UTC{}", self.iso_suffix()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ConfigError {
+    messages: Vec<String>,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, message) in self.messages.iter().enumerate() {
+            if index > 0 {
+                writeln!(formatter)?;
+            }
+            write!(formatter, "  - {message}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigLayer {
+    bind_address: Option<IpAddr>,
+    port: Option<u16>,
+    data_dir: Option<PathBuf>,
+    storage: Option<StorageBackend>,
+    static_dir: Option<PathBuf>,
+    release_notes_path: Option<PathBuf>,
+    transmission_grammar_path: Option<PathBuf>,
+    site_url: Option<String>,
+    display_timezone: Option<UtcOffset>,
+    generation_interval_secs: Option<u64>,
+    backfill_missed_transmissions: Option<bool>,
+    transmission_seed: Option<u64>,
+    admin_token: Option<String>,
+    log_format: Option<LogFormat>,
+    metrics_address: Option<SocketAddr>,
+    shutdown_timeout_secs: Option<u64>,
+}
+
+impl ConfigLayer {
+    fn set(&mut self, key: &str, value: &str, source: &str, errors: &mut Vec<String>) {
+        let value = value.trim();
+        match key {
+            "bind_address" => match value.parse() {
+                Ok(address) => self.bind_address = Some(address),
+                Err(_) => errors.push(format!("{source}: `{value}` is not an IP address")),
+            },
+            "port" => match value.parse() {
+                Ok(port) => self.port = Some(port),
+                Err(_) => errors.push(format!("{source}: `{value}` is not a valid port")),
+            },
+            "data_dir" => self.data_dir = Some(PathBuf::from(value)),
+            "storage" => match value.to_ascii_lowercase().as_str() {
+                "json" => self.storage = Some(StorageBackend::Json),
+                "sqlite" => self.storage = Some(StorageBackend::Sqlite),
+                _ => errors.push(format!(
+                    "{source}: `{value}` is not a storage backend (expected json or sqlite)"
+                )),
+            },
+            "static_dir" => self.static_dir = Some(PathBuf::from(value)),
+            "release_notes_path" => self.release_notes_path = Some(PathBuf::from(value)),
+            "transmission_grammar_path" => {
+                self.transmission_grammar_path = Some(PathBuf::from(value))
+            }
+            "site_url" => self.site_url = Some(value.to_string()),
+            "display_timezone" => match UtcOffset::parse(value) {
+                Some(offset) => self.display_timezone = Some(offset),
+                None => errors.push(format!(
+                    "{source}: `{value}` is not a timezone (expected UTC or an offset like +02:00)"
+                )),
+            },
+            "generation_interval_secs" => match value.parse() {
+                Ok(seconds) => self.generation_interval_secs = Some(seconds),
+                Err(_) => errors.push(format!("{source}: `{value}` is not a number of seconds")),
+            },
+            "backfill_missed_transmissions" => match value.to_ascii_lowercase().as_str() {
+                "true" | "yes" | "on" | "1" => self.backfill_missed_transmissions = Some(true),
+                "false" | "no" | "off" | "0" => self.backfill_missed_transmissions = Some(false),
+                _ => errors.push(format!(
+                    "{source}: `{value}` is not a boolean (expected true or false)"
+                )),
+            },
+            "transmission_seed" => match value.parse() {
+                Ok(seed) => self.transmission_seed = Some(seed),
+                Err(_) => errors.push(format!(
+                    "{source}: `{value}` is not a seed (expected an unsigned 64-bit integer)"
+                )),
+            },
+            "admin_token" => self.admin_token = Some(value.to_string()),
+            "log_format" => match value.to_ascii_lowercase().as_str() {
+                "logfmt" => self.log_format = Some(LogFormat::Logfmt),
+                "json" => self.log_format = Some(LogFormat::Json),
+                _ => errors.push(format!(
+                    "{source}: `{value}` is not a log format (expected logfmt or json)"
+                )),
+            },
+            "metrics_address" => match value.parse() {
+                Ok(address) => self.metrics_address = Some(address),
+                Err(_) => errors.push(format!(
+                    "{source}: `{value}` is not a socket address (expected an address like 127.0.0.1:9100)"
+                )),
+            },
+            "shutdown_timeout_secs" => match value.parse() {
+                Ok(seconds) => self.shutdown_timeout_secs = Some(seconds),
+                Err(_) => errors.push(format!("{source}: `{value}` is not a number of seconds")),
+            },
+            _ => errors.push(format!("{source}: unknown setting `{key}`")),
+        }
+    }
+
+    fn merge(&mut self, other: ConfigLayer) {
+        self.bind_address = other.bind_address.or(self.bind_address);
+        self.port = other.port.or(self.port);
+        self.data_dir = other.data_dir.or(self.data_dir.take());
+        self.storage = other.storage.or(self.storage);
+        self.static_dir = other.static_dir.or(self.static_dir.take());
+        self.release_notes_path = other.release_notes_path.or(self.release_notes_path.take());
+        self.transmission_grammar_path = other
+            .transmission_grammar_path
+            .or(self.transmission_grammar_path.take());
+        self.site_url = other.site_url.or(self.site_url.take());
+        self.display_timezone = other.display_timezone.or(self.display_timezone);
+        self.generation_interval_secs = other
+            .generation_interval_secs
+            .or(self.generation_interval_secs);
+        self.backfill_missed_transmissions = other
+            .backfill_missed_transmissions
+            .or(self.backfill_missed_transmissions);
+        self.transmission_seed = other.transmission_seed.or(self.transmission_seed);
+        self.admin_token = other.admin_token.or(self.admin_token.take());
+        self.log_format = other.log_format.or(self.log_format);
+        self.metrics_address = other.metrics_address.or(self.metrics_address);
+        self.shutdown_timeout_secs = other.shutdown_timeout_secs.or(self.shutdown_timeout_secs);
+    }
+}
+
+impl Config {
+    /// Loads the configuration for this process, collecting every problem
+    /// found instead of stopping at the first one.
+    pub(crate) fn load() -> Result<Config, ConfigError> {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+            print_usage();
+            std::process::exit(0);
+        }
+
+        let mut errors = Vec::new();
+        let cli = parse_args(&args, &mut errors);
+        let env = read_env(&mut errors);
+
+        let explicit_path = cli
+            .config_path
+            .clone()
+            .or_else(|| std::env::var_os("GPR_CONFIG").map(PathBuf::from));
+        let mut layer = match &explicit_path {
+            Some(path) => read_file(path, &mut errors),
+            None if Path::new(DEFAULT_CONFIG_PATH).exists() => {
+                read_file(Path::new(DEFAULT_CONFIG_PATH), &mut errors)
+            }
+            None => ConfigLayer::default(),
+        };
+        layer.merge(env);
+        layer.merge(cli.layer);
+
+        let config = Config::resolve(layer, &mut errors);
+        if errors.is_empty() {
+            Ok(config)
+        } else {
+            Err(ConfigError { messages: errors })
+        }
+    }
+
+    pub(crate) fn socket_address(&self) -> SocketAddr {
+        SocketAddr::new(self.bind_address, self.port)
+    }
+
+    pub(crate) fn transmissions_path(&self) -> PathBuf {
+        self.data_dir.join(TRANSMISSIONS_FILE_NAME)
+    }
+
+    pub(crate) fn sqlite_path(&self) -> PathBuf {
+        self.data_dir.join(SQLITE_FILE_NAME)
+    }
+
+    fn resolve(layer: ConfigLayer, errors: &mut Vec<String>) -> Config {
+        let static_dir = layer
+            .static_dir
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_STATIC_DIR));
+        let release_notes_path = layer
+            .release_notes_path
+            .unwrap_or_else(|| static_dir.join(RELEASE_NOTES_FILE_NAME));
+        let data_dir = layer
+            .data_dir
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_DATA_DIR));
+        let transmission_grammar_path = layer
+            .transmission_grammar_path
+            .unwrap_or_else(|| data_dir.join(GRAMMAR_FILE_NAME));
+        let config = Config {
+            bind_address: layer.bind_address.unwrap_or(DEFAULT_BIND_ADDRESS),
+            port: layer.port.unwrap_or(DEFAULT_PORT),
+            data_dir,
+            storage: layer.storage.unwrap_or_default(),
+            static_dir,
+            release_notes_path,
+            transmission_grammar_path,
+            site_url: layer
+                .site_url
+                .unwrap_or_else(|| DEFAULT_SITE_URL.to_string())
+                .trim_end_matches('/')
+                .to_string(),
+            display_timezone: layer.display_timezone.unwrap_or_default(),
+            generation_interval_secs: layer
+                .generation_interval_secs
+                .unwrap_or(DEFAULT_GENERATION_INTERVAL_SECS),
+            backfill_missed_transmissions: layer.backfill_missed_transmissions.unwrap_or(false),
+            transmission_seed: layer.transmission_seed,
+            admin_token: layer.admin_token,
+            log_format: layer.log_format.unwrap_or_default(),
+            metrics_address: layer.metrics_address,
+            shutdown_timeout_secs: layer
+                .shutdown_timeout_secs
+                .unwrap_or(DEFAULT_SHUTDOWN_TIMEOUT_SECS),
+        };
+
+        if !config.site_url.starts_with("http://") && !config.site_url.starts_with("https://") {
+            errors.push(format!(
+                "site_url: `{}` must start with http:// or https://",
+                config.site_url
+            ));
+        }
+        if config.generation_interval_secs == 0 {
+            errors.push("generation_interval_secs: must be greater than zero".to_string());
+        }
+        if !config.static_dir.is_dir() {
+            errors.push(format!(
+                "static_dir: `{}` is not a directory",
+                config.static_dir.display()
+            ));
+        }
+        if config.data_dir.exists() && !config.data_dir.is_dir() {
+            errors.push(format!(
+                "data_dir: `{}` exists but is not a directory",
+                config.data_dir.display()
+            ));
+        }
+        if config.release_notes_path.is_dir() {
+            errors.push(format!(
+                "release_notes_path: `{}` is a directory",
+                config.release_notes_path.display()
+            ));
+        }
+
+        if config
+            .admin_token
+            .as_ref()
+            .is_some_and(|token| token.trim().len() < MIN_ADMIN_TOKEN_LEN)
+        {
+            errors.push(format!(
+                "admin_token: must be at least {MIN_ADMIN_TOKEN_LEN} characters"
+            ));
+        }
+        if config.metrics_address == Some(config.socket_address()) {
+            errors.push(format!(
+                "metrics_address: `{}` is already the site's address; leave it unset to serve /metrics there",
+                config.socket_address()
+            ));
+        }
+        if config.transmission_grammar_path.is_dir() {
+            errors.push(format!(
+                "transmission_grammar_path: `{}` is a directory",
+                config.transmission_grammar_path.display()
+            ));
+        }
+
+        config
+    }
+}
+
+struct CommandLine {
+    config_path: Option<PathBuf>,
+    layer: ConfigLayer,
+}
+
+fn parse_args(args: &[String], errors: &mut Vec<String>) -> CommandLine {
+    let mut command_line = CommandLine {
+        config_path: None,
+        layer: ConfigLayer::default(),
+    };
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) => (flag, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+        let Some(value) = inline_value.or_else(|| args.next().cloned()) else {
+            errors.push(format!("{flag}: missing value"));
+            continue;
+        };
+
+        if flag == "--config" {
+            command_line.config_path = Some(PathBuf::from(value));
+            continue;
+        }
+
+        match SETTINGS.iter().find(|(_, _, name)| *name == flag) {
+            Some((key, _, _)) => command_line.layer.set(key, &value, flag, errors),
+            None => errors.push(format!("{flag}: unknown command line flag")),
+        }
+    }
+
+    command_line
+}
+
+fn read_env(errors: &mut Vec<String>) -> ConfigLayer {
+    let mut layer = ConfigLayer::default();
+
+    // `SITE_URL` predates the `GPR_` prefix and is still honoured.
+    if let Ok(value) = std::env::var("SITE_URL") {
+        layer.set("site_url", &value, "SITE_URL", errors);
+    }
+
+    for (key, variable, _) in SETTINGS {
+        if let Ok(value) = std::env::var(variable) {
+            layer.set(key, &value, variable, errors);
+        }
+    }
+
+    layer
+}
+
+fn read_file(path: &Path, errors: &mut Vec<String>) -> ConfigLayer {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(error) => {
+            errors.push(format!("{}: {error}", path.display()));
+            return ConfigLayer::default();
+        }
+    };
+
+    match toml::from_str(&content) {
+        Ok(layer) => layer,
+        Err(error) => {
+            errors.push(format!("{}: {}", path.display(), error.message()));
+            ConfigLayer::default()
+        }
+    }
+}
+
+fn print_usage() {
+    println!("Usage: galacticpirateradio_site [--config <path>] [--<setting> <value>]...");
+    println!();
+    println!("Settings (command line flag, environment variable):");
+    for (_, variable, flag) in SETTINGS {
+        println!("  {flag:<28} {variable}");
+    }
+    println!();
+    println!("Settings may also be given in a TOML file (default: {DEFAULT_CONFIG_PATH}).");
+}
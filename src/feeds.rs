@@ -0,0 +1,210 @@
+use crate::{ReleaseEntry, TransmissionEntry, rfc2822_from_unix, rfc3339_from_unix};
+
+const FEED_TITLE: &str = "Ethereal Waves Changelog";
+const FEED_SUBTITLE: &str = "Release history for Ethereal Waves, the Linux music player built with libcosmic and GStreamer.";
+const FEED_AUTHOR: &str = "LotusPetal";
+
+pub(crate) fn release_notes_atom(
+    changelog_url: &str,
+    feed_url: &str,
+    updated_at: u64,
+    entries: &[ReleaseEntry],
+) -> String {
+    let updated = rfc3339_from_unix(updated_at);
+    let mut body = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>{title}</title>
+  <subtitle>{subtitle}</subtitle>
+  <link href="{changelog}" rel="alternate" type="text/html" />
+  <link href="{feed}" rel="self" type="application/atom+xml" />
+  <id>{changelog}</id>
+  <updated>{updated}</updated>
+  <author>
+    <name>{author}</name>
+  </author>
+"#,
+        title = escape_xml(FEED_TITLE),
+        subtitle = escape_xml(FEED_SUBTITLE),
+        changelog = escape_xml(changelog_url),
+        feed = escape_xml(feed_url),
+        author = escape_xml(FEED_AUTHOR),
+    );
+
+    for entry in entries {
+        let id = format!("{changelog_url}#{}", entry.anchor_id);
+        let link = format!("{changelog_url}/{}", entry.version);
+        body.push_str(&format!(
+            r#"  <entry>
+    <title>Ethereal Waves {version}{status}</title>
+    <link href="{link}" rel="alternate" type="text/html" />
+    <id>{id}</id>
+    <updated>{entry_updated}</updated>
+    <content type="html">{content}</content>
+  </entry>
+"#,
+            version = escape_xml(&entry.version),
+            status = release_status_suffix(entry),
+            id = escape_xml(&id),
+            link = escape_xml(&link),
+            entry_updated = rfc3339_from_unix(entry.released_at.unwrap_or(updated_at)),
+            content = escape_xml(&release_entry_html(entry)),
+        ));
+    }
+
+    body.push_str("</feed>\n");
+    body
+}
+
+pub(crate) fn release_notes_rss(
+    changelog_url: &str,
+    feed_url: &str,
+    updated_at: u64,
+    entries: &[ReleaseEntry],
+) -> String {
+    let mut body = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom">
+  <channel>
+    <title>{title}</title>
+    <link>{changelog}</link>
+    <description>{subtitle}</description>
+    <language>en</language>
+    <lastBuildDate>{updated}</lastBuildDate>
+    <atom:link href="{feed}" rel="self" type="application/rss+xml" />
+"#,
+        title = escape_xml(FEED_TITLE),
+        subtitle = escape_xml(FEED_SUBTITLE),
+        changelog = escape_xml(changelog_url),
+        feed = escape_xml(feed_url),
+        updated = rfc2822_from_unix(updated_at),
+    );
+
+    for entry in entries {
+        let id = format!("{changelog_url}#{}", entry.anchor_id);
+        let link = format!("{changelog_url}/{}", entry.version);
+        body.push_str(&format!(
+            r#"    <item>
+      <title>Ethereal Waves {version}{status}</title>
+      <link>{link}</link>
+      <guid isPermaLink="true">{id}</guid>
+{pub_date}      <description>{content}</description>
+    </item>
+"#,
+            version = escape_xml(&entry.version),
+            status = release_status_suffix(entry),
+            id = escape_xml(&id),
+            link = escape_xml(&link),
+            pub_date = entry
+                .released_at
+                .map(|released_at| format!(
+                    "      <pubDate>{}</pubDate>\n",
+                    rfc2822_from_unix(released_at)
+                ))
+                .unwrap_or_default(),
+            content = escape_xml(&release_entry_html(entry)),
+        ));
+    }
+
+    body.push_str("  </channel>\n</rss>\n");
+    body
+}
+
+pub(crate) fn transmissions_atom(
+    site_url: &str,
+    feed_url: &str,
+    updated_at: u64,
+    entries: &[TransmissionEntry],
+) -> String {
+    let archive_url = format!("{site_url}/transmissions");
+    let mut body = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Galactic Pirate Radio Transmissions</title>
+  <subtitle>The Galactic Pirate Radio station log.</subtitle>
+  <link href="{archive}" rel="alternate" type="text/html" />
+  <link href="{feed}" rel="self" type="application/atom+xml" />
+  <id>{archive}</id>
+  <updated>{updated}</updated>
+  <author>
+    <name>{author}</name>
+  </author>
+"#,
+        archive = escape_xml(&archive_url),
+        feed = escape_xml(feed_url),
+        updated = rfc3339_from_unix(updated_at),
+        author = escape_xml(FEED_AUTHOR),
+    );
+
+    for entry in entries {
+        // The permalink is derived from the timestamp alone, so it stays a
+        // stable entry ID for feed readers.
+        let link = format!("{archive_url}/{}", entry.timestamp);
+        let related = entry.link.as_ref().map_or_else(String::new, |link| {
+            let url = if link.starts_with('/') {
+                format!("{site_url}{link}")
+            } else {
+                link.clone()
+            };
+            format!(
+                "    <link href=\"{}\" rel=\"related\" type=\"text/html\" />\n",
+                escape_xml(&url)
+            )
+        });
+        body.push_str(&format!(
+            r#"  <entry>
+    <title>{title}</title>
+    <link href="{link}" rel="alternate" type="text/html" />
+{related}    <id>{link}</id>
+    <updated>{updated}</updated>
+    <content type="text">{title}</content>
+  </entry>
+"#,
+            title = escape_xml(&entry.message),
+            link = escape_xml(&link),
+            updated = rfc3339_from_unix(entry.timestamp),
+        ));
+    }
+
+    body.push_str("</feed>\n");
+    body
+}
+
+fn release_status_suffix(entry: &ReleaseEntry) -> &'static str {
+    match (entry.yanked, entry.prerelease) {
+        (true, _) => " (yanked)",
+        (false, true) => " (pre-release)",
+        (false, false) => "",
+    }
+}
+
+fn release_entry_html(entry: &ReleaseEntry) -> String {
+    let mut html = String::new();
+
+    for section in &entry.sections {
+        html.push_str(&format!("<h3>{}</h3><ul>", escape_xml(&section.title)));
+        for item in &section.items {
+            html.push_str(&format!("<li>{}{}</li>", item.html, item.children_html()));
+        }
+        html.push_str("</ul>");
+    }
+
+    html
+}
+
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for character in value.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(character),
+        }
+    }
+
+    escaped
+}
@@ -0,0 +1,187 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+const BUNDLED_GRAMMAR: &str = include_str!("../data/transmission_grammar.toml");
+const START_RULE: &str = "message";
+const RELEASE_RULE: &str = "release";
+/// Placeholders filled in by the site rather than by a rule.
+const VARIABLES: [&str; 1] = ["version"];
+/// Deeper placeholders are left as written, so a rule that refers back to
+/// itself cannot expand forever.
+const MAX_DEPTH: usize = 12;
+
+/// Weighted rules that expand `{placeholder}`s into station log messages,
+/// loaded from `data/transmission_grammar.toml`.
+#[derive(Debug)]
+pub(crate) struct Grammar {
+    rules: HashMap<String, Vec<Choice>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum RawChoice {
+    Text(String),
+    Weighted {
+        text: String,
+        #[serde(default = "default_weight")]
+        weight: f64,
+    },
+}
+
+#[derive(Debug)]
+struct Choice {
+    text: String,
+    weight: f64,
+}
+
+fn default_weight() -> f64 {
+    1.0
+}
+
+impl Grammar {
+    /// The grammar shipped with the site, used until a file has loaded.
+    pub(crate) fn bundled() -> Grammar {
+        Grammar::parse(BUNDLED_GRAMMAR).expect("bundled transmission grammar is valid")
+    }
+
+    /// Parses and validates a grammar, reporting every problem at once.
+    pub(crate) fn parse(source: &str) -> Result<Grammar, String> {
+        let raw: HashMap<String, Vec<RawChoice>> =
+            toml::from_str(source).map_err(|error| error.message().to_string())?;
+        let rules: HashMap<String, Vec<Choice>> = raw
+            .into_iter()
+            .map(|(name, choices)| {
+                let choices = choices
+                    .into_iter()
+                    .map(|choice| match choice {
+                        RawChoice::Text(text) => Choice { text, weight: 1.0 },
+                        RawChoice::Weighted { text, weight } => Choice { text, weight },
+                    })
+                    .collect();
+                (name, choices)
+            })
+            .collect();
+
+        let mut errors = Vec::new();
+        if !rules.contains_key(START_RULE) {
+            errors.push(format!("missing the `{START_RULE}` rule"));
+        }
+        for (name, choices) in &rules {
+            if choices.is_empty() {
+                errors.push(format!("`{name}` has no choices"));
+            }
+            for choice in choices {
+                if !choice.weight.is_finite() || choice.weight <= 0.0 {
+                    errors.push(format!(
+                        "`{name}`: weight of \"{}\" must be greater than zero",
+                        choice.text
+                    ));
+                }
+                for placeholder in placeholders(&choice.text) {
+                    if !rules.contains_key(placeholder) && !VARIABLES.contains(&placeholder) {
+                        errors.push(format!(
+                            "`{name}`: \"{}\" refers to unknown rule `{placeholder}`",
+                            choice.text
+                        ));
+                    }
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(Grammar { rules })
+        } else {
+            errors.sort();
+            Err(errors.join("; "))
+        }
+    }
+
+    /// Expands the `message` rule, drawing every choice from `next_random`.
+    pub(crate) fn generate(&self, next_random: &mut impl FnMut() -> u64) -> String {
+        let mut message = String::new();
+        self.expand(START_RULE, &[], 0, next_random, &mut message);
+        message
+    }
+
+    /// Expands the optional `release` rule with `{version}` set, or returns
+    /// `None` when the grammar has no such rule.
+    pub(crate) fn release_announcement(
+        &self,
+        version: &str,
+        next_random: &mut impl FnMut() -> u64,
+    ) -> Option<String> {
+        if !self.rules.contains_key(RELEASE_RULE) {
+            return None;
+        }
+
+        let mut message = String::new();
+        self.expand(
+            RELEASE_RULE,
+            &[("version", version)],
+            0,
+            next_random,
+            &mut message,
+        );
+        Some(message)
+    }
+
+    fn expand(
+        &self,
+        rule: &str,
+        variables: &[(&str, &str)],
+        depth: usize,
+        next_random: &mut impl FnMut() -> u64,
+        output: &mut String,
+    ) {
+        let Some(choices) = self.rules.get(rule) else {
+            return;
+        };
+        let text = &pick_weighted(choices, next_random()).text;
+
+        let mut rest = text.as_str();
+        while let Some(start) = rest.find('{') {
+            output.push_str(&rest[..start]);
+            let after_brace = &rest[start + 1..];
+            let name = after_brace.find('}').map(|end| &after_brace[..end]);
+            let variable = variables
+                .iter()
+                .find(|(variable, _)| Some(*variable) == name);
+            match name {
+                Some(name) if let Some((_, value)) = variable => {
+                    output.push_str(value);
+                    rest = &after_brace[name.len() + 1..];
+                }
+                Some(name) if depth < MAX_DEPTH && self.rules.contains_key(name) => {
+                    self.expand(name, variables, depth + 1, next_random, output);
+                    rest = &after_brace[name.len() + 1..];
+                }
+                _ => {
+                    output.push('{');
+                    rest = after_brace;
+                }
+            }
+        }
+        output.push_str(rest);
+    }
+}
+
+fn pick_weighted(choices: &[Choice], random: u64) -> &Choice {
+    let total: f64 = choices.iter().map(|choice| choice.weight).sum();
+    // The top 53 bits give a uniform float in [0, 1).
+    let mut target = (random >> 11) as f64 / (1u64 << 53) as f64 * total;
+
+    for choice in choices {
+        if target < choice.weight {
+            return choice;
+        }
+        target -= choice.weight;
+    }
+
+    &choices[choices.len() - 1]
+}
+
+fn placeholders(text: &str) -> impl Iterator<Item = &str> {
+    text.split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+}
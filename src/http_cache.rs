@@ -0,0 +1,238 @@
+use axum::{
+    body::{Body, to_bytes},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::UNIX_EPOCH;
+use tracing::error;
+
+/// Pages are rendered in memory, so this only guards against surprises.
+const MAX_BUFFERED_BODY: usize = 16 * 1024 * 1024;
+const NO_STORE: &str = "no-store";
+const IMMUTABLE_ASSET: &str = "public, max-age=31536000, immutable";
+const UNVERSIONED_ASSET: &str = "public, max-age=3600";
+
+static STATIC_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// How responses on a route may be cached by browsers and the CDN.
+#[derive(Clone, Debug)]
+pub(crate) enum CachePolicy {
+    /// Never stored, for responses that must always be fresh.
+    NoStore,
+    /// Cached for `max_age` seconds, then revalidated against an ETag of the
+    /// body and, when known, the time its source last changed.
+    Revalidate {
+        max_age: u64,
+        last_modified: Option<u64>,
+    },
+    /// A file from the static directory, by its still percent-encoded path
+    /// below `/static/`. Requests whose `?v=` matches the file's fingerprint
+    /// are cached for a year.
+    StaticAsset {
+        path: String,
+        versioned: Option<String>,
+    },
+}
+
+/// Records where `asset_url` looks up fingerprints; called once at startup.
+pub(crate) fn init(static_dir: PathBuf) {
+    let _ = STATIC_DIR.set(static_dir);
+}
+
+/// The URL of a file in the static directory with a fingerprint of its
+/// current version, so it can be cached until the file changes. Used from
+/// templates.
+pub(crate) fn asset_url(path: &str) -> String {
+    let url = format!("/static/{}", path.replace(' ', "%20"));
+    match asset_fingerprint(path) {
+        Some(fingerprint) => format!("{url}?v={fingerprint}"),
+        None => url,
+    }
+}
+
+/// Applies `policy` to a response, answering `304 Not Modified` when the
+/// request's `If-None-Match` or `If-Modified-Since` shows the client already
+/// has this version. Apart from `NoStore`, only successful responses are
+/// touched.
+pub(crate) async fn apply(
+    policy: CachePolicy,
+    request_headers: &HeaderMap,
+    response: Response,
+) -> Response {
+    let status = response.status();
+    match policy {
+        CachePolicy::NoStore => with_cache_control(response, NO_STORE),
+        CachePolicy::Revalidate {
+            max_age,
+            last_modified,
+        } if status == StatusCode::OK => {
+            revalidate(request_headers, response, max_age, last_modified).await
+        }
+        // `ServeDir` answers `If-Modified-Since` itself; its 304s still need
+        // the caching headers.
+        CachePolicy::StaticAsset { path, versioned }
+            if status == StatusCode::OK || status == StatusCode::NOT_MODIFIED =>
+        {
+            static_asset(request_headers, response, &path, versioned)
+        }
+        _ => response,
+    }
+}
+
+async fn revalidate(
+    request_headers: &HeaderMap,
+    response: Response,
+    max_age: u64,
+    last_modified: Option<u64>,
+) -> Response {
+    let (mut parts, body) = response.into_parts();
+    let body = match to_bytes(body, MAX_BUFFERED_BODY).await {
+        Ok(body) => body,
+        Err(error) => {
+            error!(%error, "failed to buffer response for caching");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let headers = &mut parts.headers;
+    set_header(
+        headers,
+        header::CACHE_CONTROL,
+        &format!("public, max-age={max_age}"),
+    );
+    set_header(headers, header::ETAG, &format!("\"{:016x}\"", fnv1a(&body)));
+    if let Some(last_modified) = last_modified {
+        set_header(
+            headers,
+            header::LAST_MODIFIED,
+            &crate::http_date_from_unix(last_modified),
+        );
+    }
+
+    if is_not_modified(request_headers, headers) {
+        return not_modified(headers);
+    }
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// The ETag of a static file is its fingerprint, derived from its size and
+/// modification time, so no file has to be read to compute it.
+fn static_asset(
+    request_headers: &HeaderMap,
+    mut response: Response,
+    path: &str,
+    versioned: Option<String>,
+) -> Response {
+    let fingerprint = percent_decode(path).and_then(|path| asset_fingerprint(&path));
+    let cache_control = match (&fingerprint, &versioned) {
+        (Some(fingerprint), Some(versioned)) if fingerprint == versioned => IMMUTABLE_ASSET,
+        _ => UNVERSIONED_ASSET,
+    };
+
+    let headers = response.headers_mut();
+    set_header(headers, header::CACHE_CONTROL, cache_control);
+    if let Some(fingerprint) = fingerprint {
+        set_header(headers, header::ETAG, &format!("\"{fingerprint}\""));
+    }
+
+    if response.status() == StatusCode::OK && is_not_modified(request_headers, response.headers()) {
+        return not_modified(response.headers());
+    }
+    response
+}
+
+fn with_cache_control(mut response: Response, value: &str) -> Response {
+    set_header(response.headers_mut(), header::CACHE_CONTROL, value);
+    response
+}
+
+/// `If-None-Match` takes precedence over `If-Modified-Since`, as RFC 9110
+/// requires.
+fn is_not_modified(request_headers: &HeaderMap, response_headers: &HeaderMap) -> bool {
+    let response_value = |name| {
+        response_headers
+            .get(name)
+            .and_then(|value: &HeaderValue| value.to_str().ok())
+    };
+
+    if let Some(if_none_match) = request_headers.get(header::IF_NONE_MATCH) {
+        let Some(etag) = response_value(header::ETAG) else {
+            return false;
+        };
+        return if_none_match.to_str().is_ok_and(|candidates| {
+            candidates
+                .split(',')
+                .map(str::trim)
+                .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == etag)
+        });
+    }
+
+    let since = request_headers
+        .get(header::IF_MODIFIED_SINCE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(crate::unix_from_http_date);
+    let last_modified = response_value(header::LAST_MODIFIED).and_then(crate::unix_from_http_date);
+    matches!((since, last_modified), (Some(since), Some(last_modified)) if last_modified <= since)
+}
+
+fn not_modified(headers: &HeaderMap) -> Response {
+    let mut response = StatusCode::NOT_MODIFIED.into_response();
+    for name in [
+        header::CACHE_CONTROL,
+        header::ETAG,
+        header::LAST_MODIFIED,
+        header::VARY,
+    ] {
+        if let Some(value) = headers.get(&name) {
+            response.headers_mut().insert(name, value.clone());
+        }
+    }
+    response
+}
+
+fn set_header(headers: &mut HeaderMap, name: header::HeaderName, value: &str) {
+    if let Ok(value) = HeaderValue::from_str(value) {
+        headers.insert(name, value);
+    }
+}
+
+/// Fingerprints a file below the static directory by its size and
+/// modification time.
+fn asset_fingerprint(path: &str) -> Option<String> {
+    if path.split('/').any(|segment| segment == "..") {
+        return None;
+    }
+    let metadata = std::fs::metadata(STATIC_DIR.get()?.join(path)).ok()?;
+    let modified_at = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+
+    let mut bytes = [0u8; 16];
+    bytes[..8].copy_from_slice(&modified_at.as_secs().to_le_bytes());
+    bytes[8..].copy_from_slice(&metadata.len().to_le_bytes());
+    Some(format!("{:012x}", fnv1a(&bytes) >> 16))
+}
+
+fn percent_decode(path: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut rest = path.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// 64-bit FNV-1a: stable across builds and restarts, unlike the standard
+/// library's hasher.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
@@ -1,98 +1,620 @@
 use askama::Template;
 use axum::{
-    Router,
-    extract::State,
-    http::{StatusCode, header},
-    response::{Html, IntoResponse, Redirect, Response},
-    routing::get,
+    Json, Router,
+    extract::{MatchedPath, Path as UrlPath, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    middleware::{self, Next},
+    response::{
+        Html, IntoResponse, Redirect, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
+    routing::{delete, get, post},
 };
+use config::{Config, UtcOffset};
+use futures_util::stream::{self, Stream, StreamExt};
+use grammar::Grammar;
+use http_cache::CachePolicy;
+use metrics::METRICS;
 use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
 use std::fs;
-use std::net::SocketAddr;
+use std::future::IntoFuture;
 use std::path::Path;
 use std::sync::Arc;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use tokio::sync::RwLock;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use store::TransmissionStore;
+use tokio::sync::{RwLock, broadcast, watch};
+use tokio::task::JoinHandle;
+use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
 use tower_http::services::ServeDir;
+use tower_http::trace::TraceLayer;
+use tracing::{Span, error, info, info_span, instrument, warn};
+use version::SemanticVersion;
+
+mod config;
+mod feeds;
+mod grammar;
+mod http_cache;
+mod logging;
+mod markdown;
+mod metrics;
+mod store;
+mod version;
 
-const TRANSMISSIONS_PATH: &str = "data/recent_transmissions.json";
-const RELEASE_NOTES_PATH: &str = "static/Ethereal Waves - Release Notes.md";
-const GENERATION_INTERVAL_SECS: u64 = 3 * 60 * 60;
 const MAX_TRANSMISSIONS: usize = 12;
-const DEFAULT_SITE_URL: &str = "http://127.0.0.1:3000";
+const TRANSMISSIONS_PER_PAGE: usize = 20;
+const TRANSMISSIONS_API_DEFAULT_LIMIT: usize = 20;
+const TRANSMISSIONS_API_MAX_LIMIT: usize = 100;
+const TRANSMISSION_EVENTS_CAPACITY: usize = 16;
+/// A new message must differ from this many of the most recent entries.
+const RECENT_DUPLICATE_WINDOW: usize = MAX_TRANSMISSIONS;
+const MAX_GENERATION_ATTEMPTS: usize = 64;
+const MAX_MANUAL_MESSAGE_CHARS: usize = 500;
+/// The generator wakes at least this often, even between due transmissions,
+/// to pick up new releases.
+const GENERATOR_TICK_SECS: u64 = 300;
+/// Waits between attempts to read unreadable release notes, doubling from
+/// the minimum up to the maximum.
+const RELEASE_NOTES_RETRY_MIN_SECS: u64 = 1;
+const RELEASE_NOTES_RETRY_MAX_SECS: u64 = 60;
+const TRANSMISSION_PAGES_MAX_AGE_SECS: u64 = 60;
+const RELEASE_PAGES_MAX_AGE_SECS: u64 = 300;
+const SITE_FILES_MAX_AGE_SECS: u64 = 3600;
+/// `/readyz` fails once the generator has missed this many ticks in a row.
+const GENERATOR_MISSED_TICKS_UNREADY: u64 = 2;
 const HOME_OG_IMAGE_PATH: &str = "/static/images/gpr.png";
 const ETHEREAL_WAVES_OG_IMAGE_PATH: &str = "/static/images/Ethereal%20Waves%20-%20Dark%20Mode.png";
+/// Month abbreviations used by RFC 2822 and HTTP dates.
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
 
 #[derive(Clone)]
 struct AppState {
     transmissions: Arc<RwLock<TransmissionState>>,
-    site_url: String,
+    store: Arc<dyn TransmissionStore>,
+    release_notes: Arc<RwLock<ReleaseNotesCache>>,
+    transmission_grammar: Arc<RwLock<TransmissionGrammarCache>>,
+    config: Arc<Config>,
+    transmission_events: broadcast::Sender<TransmissionEntry>,
+    shutdown: watch::Receiver<bool>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
 struct TransmissionState {
     last_generated_at: u64,
+    /// The seed the generator was started from; `rng_state` is where it has
+    /// got to, so a restart continues the same sequence.
+    #[serde(default)]
+    seed: Option<u64>,
+    #[serde(default)]
+    rng_state: u64,
+    /// The newest Ethereal Waves version already announced on the log.
+    #[serde(default)]
+    last_seen_version: Option<String>,
+    /// When the log last gained or lost an entry, for `Last-Modified`. State
+    /// saved before this was tracked loads as zero.
+    #[serde(default)]
+    modified_at: u64,
     entries: Vec<TransmissionEntry>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
 struct TransmissionEntry {
     timestamp: u64,
-    time_label: String,
     message: String,
+    #[serde(default)]
+    kind: TransmissionKind,
+    /// Site-relative page the transmission is about, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    link: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum TransmissionKind {
+    /// Flavour text from the transmission grammar.
+    #[default]
+    Generated,
+    /// An announcement of a new Ethereal Waves release.
+    Release,
+    /// Posted by hand through `POST /admin/transmissions`.
+    Manual,
+}
+
+impl TransmissionKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            TransmissionKind::Generated => "generated",
+            TransmissionKind::Release => "release",
+            TransmissionKind::Manual => "manual",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "generated" => Some(TransmissionKind::Generated),
+            "release" => Some(TransmissionKind::Release),
+            "manual" => Some(TransmissionKind::Manual),
+            _ => None,
+        }
+    }
+}
+
+/// A transmission as shown to visitors, with its time in the display
+/// timezone and relative to when the page was rendered.
+#[derive(Clone, Serialize)]
+struct TransmissionView {
+    #[serde(flatten)]
+    entry: TransmissionEntry,
+    /// ISO-8601 timestamp with the display timezone's offset.
+    datetime: String,
+    /// Absolute date and time, e.g. `2026-04-09 05:36:59 UTC+02:00`.
+    time_label: String,
+    /// How long ago the transmission arrived, e.g. `3 days ago`.
+    relative_label: String,
+}
+
+impl TransmissionView {
+    fn new(entry: TransmissionEntry, timezone: UtcOffset, now: u64) -> Self {
+        let local_seconds = entry.timestamp as i64 + timezone.seconds();
+        let (year, month, day) = civil_date_from_unix_days(local_seconds.div_euclid(86_400));
+        let clock = clock_label_from_unix(local_seconds.rem_euclid(86_400) as u64);
+        let date = format!("{year:04}-{month:02}-{day:02}");
+
+        Self {
+            datetime: format!("{date}T{clock}{}", timezone.iso_suffix()),
+            time_label: format!("{date} {clock} {timezone}"),
+            relative_label: relative_time_label(entry.timestamp, now, &date),
+            entry,
+        }
+    }
+}
+
+fn transmission_views(
+    entries: impl IntoIterator<Item = TransmissionEntry>,
+    app_state: &AppState,
+) -> Vec<TransmissionView> {
+    let now = unix_now_secs();
+    entries
+        .into_iter()
+        .map(|entry| TransmissionView::new(entry, app_state.config.display_timezone, now))
+        .collect()
+}
+
+/// `just now`, `5 min ago`, `2 h ago` or `3 days ago`; after a month the
+/// plain `date` reads better.
+fn relative_time_label(timestamp: u64, now: u64, date: &str) -> String {
+    let elapsed = now.saturating_sub(timestamp);
+    match elapsed {
+        0..60 => "just now".to_string(),
+        60..3_600 => format!("{} min ago", elapsed / 60),
+        3_600..86_400 => format!("{} h ago", elapsed / 3_600),
+        86_400..172_800 => "1 day ago".to_string(),
+        172_800..2_592_000 => format!("{} days ago", elapsed / 86_400),
+        _ => date.to_string(),
+    }
 }
 
 #[tokio::main]
 async fn main() {
-    let loaded = load_transmissions();
-    let site_url = std::env::var("SITE_URL")
-        .unwrap_or_else(|_| DEFAULT_SITE_URL.to_string())
-        .trim_end_matches('/')
-        .to_string();
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("invalid configuration:\n{error}");
+            std::process::exit(2);
+        }
+    };
+    logging::init(config.log_format);
+    http_cache::init(config.static_dir.clone());
+    let store = store::open(&config).unwrap_or_else(|error| {
+        error!(%error, "failed to open transmission store");
+        std::process::exit(1);
+    });
+    let loaded = load_or_default_transmissions(store.as_ref(), config.transmission_seed);
+    let (shutdown_sender, shutdown_receiver) = watch::channel(false);
     let state = AppState {
         transmissions: Arc::new(RwLock::new(loaded)),
-        site_url,
+        store,
+        release_notes: Arc::new(RwLock::new(ReleaseNotesCache::default())),
+        transmission_grammar: Arc::new(RwLock::new(TransmissionGrammarCache {
+            modified_at: None,
+            grammar: Arc::new(Grammar::bundled()),
+        })),
+        config: Arc::new(config),
+        transmission_events: broadcast::channel(TRANSMISSION_EVENTS_CAPACITY).0,
+        shutdown: shutdown_receiver.clone(),
     };
 
-    generate_if_needed_and_persist(&state).await;
-    start_transmission_generator(state.clone());
+    load_release_notes(&state).await;
+    load_transmission_grammar(&state).await;
 
-    let app = Router::new()
+    // Catch up before the first request, so pages are fresh from the start.
+    run_generator_tick(&state).await;
+    let generator = start_transmission_generator(state.clone(), shutdown_receiver.clone());
+
+    let mut routes = Router::new()
         .route("/", get(index))
         .route("/ethereal-waves", get(ethereal_waves))
         .route("/ethereal-waves/", get(ethereal_waves))
         .route("/ethereal-waves/changelog", get(ethereal_waves_changelog))
         .route("/ethereal-waves/changelog/", get(ethereal_waves_changelog))
+        .route(
+            "/ethereal-waves/changelog/compare",
+            get(ethereal_waves_changelog_compare),
+        )
+        .route(
+            "/ethereal-waves/changelog/compare/",
+            get(ethereal_waves_changelog_compare),
+        )
+        .route(
+            "/ethereal-waves/changelog/{version}",
+            get(ethereal_waves_release),
+        )
+        .route(
+            "/ethereal-waves/changelog/{version}/",
+            get(ethereal_waves_release),
+        )
+        .route(
+            "/ethereal-waves/changelog.atom",
+            get(ethereal_waves_changelog_atom),
+        )
+        .route(
+            "/ethereal-waves/changelog.atom/",
+            get(ethereal_waves_changelog_atom),
+        )
+        .route(
+            "/ethereal-waves/changelog.rss",
+            get(ethereal_waves_changelog_rss),
+        )
+        .route(
+            "/ethereal-waves/changelog.rss/",
+            get(ethereal_waves_changelog_rss),
+        )
+        .route("/api/ethereal-waves/releases", get(api_releases))
+        .route("/api/ethereal-waves/releases/", get(api_releases))
+        .route("/api/ethereal-waves/releases/{version}", get(api_release))
+        .route("/api/ethereal-waves/releases/{version}/", get(api_release))
         .route("/software", get(legacy_software_redirect))
         .route("/software/", get(legacy_software_redirect))
+        .route("/transmissions.atom", get(transmissions_atom))
+        .route("/transmissions.atom/", get(transmissions_atom))
+        .route("/api/transmissions", get(api_transmissions))
+        .route("/api/transmissions/", get(api_transmissions))
+        .route("/transmissions/stream", get(transmissions_stream))
+        .route("/transmissions/stream/", get(transmissions_stream))
+        .merge(
+            Router::new()
+                .route("/admin/transmissions", post(admin_post_transmission))
+                .route("/admin/transmissions/", post(admin_post_transmission))
+                .route(
+                    "/admin/transmissions/{timestamp}",
+                    delete(admin_delete_transmission),
+                )
+                .route(
+                    "/admin/transmissions/{timestamp}/",
+                    delete(admin_delete_transmission),
+                )
+                .route_layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    require_admin_token,
+                )),
+        )
+        .route("/transmissions", get(transmissions_archive))
+        .route("/transmissions/", get(transmissions_archive))
+        .route("/transmissions/{timestamp}", get(transmission_permalink))
+        .route("/transmissions/{timestamp}/", get(transmission_permalink))
         .route("/robots.txt", get(robots_txt))
         .route("/robots.txt/", get(robots_txt))
         .route("/sitemap.xml", get(sitemap_xml))
         .route("/sitemap.xml/", get(sitemap_xml))
-        .nest_service("/static", ServeDir::new("static"))
+        .route("/healthz", get(healthz))
+        .route("/healthz/", get(healthz))
+        .route("/readyz", get(readyz))
+        .route("/readyz/", get(readyz));
+    if state.config.metrics_address.is_none() {
+        routes = routes
+            .route("/metrics", get(metrics_handler))
+            .route("/metrics/", get(metrics_handler));
+    }
+    let app = routes
+        .nest_service("/static", ServeDir::new(&state.config.static_dir))
         .fallback(not_found)
-        .with_state(state);
+        .with_state(state.clone())
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            apply_cache_policy,
+        ))
+        .layer(middleware::from_fn(track_request_metrics))
+        .layer(
+            TraceLayer::new_for_http()
+                .make_span_with(request_span)
+                .on_request(())
+                .on_response(log_response)
+                .on_failure(()),
+        )
+        .layer(PropagateRequestIdLayer::x_request_id())
+        .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid));
+
+    let metrics_server = match state.config.metrics_address {
+        Some(address) => Some(serve_metrics(address, shutdown_receiver.clone()).await),
+        None => None,
+    };
 
-    let address = SocketAddr::from(([127, 0, 0, 1], 3000));
-    println!("Listening on http://{address}");
+    let address = state.config.socket_address();
+    info!(%address, "listening");
 
     let listener = tokio::net::TcpListener::bind(address)
         .await
         .expect("failed to bind server address");
 
-    axum::serve(listener, app).await.expect("server error");
+    let mut server_shutdown = shutdown_receiver;
+    let mut server = tokio::spawn(
+        axum::serve(listener, app)
+            .with_graceful_shutdown(async move {
+                let _ = server_shutdown.changed().await;
+            })
+            .into_future(),
+    );
+
+    tokio::select! {
+        result = &mut server => {
+            result
+                .expect("server task panicked")
+                .expect("server error");
+            return;
+        }
+        () = shutdown_signal() => {}
+    }
+
+    info!("shutting down; draining in-flight requests");
+    let _ = shutdown_sender.send(true);
+
+    let drain_timeout = Duration::from_secs(state.config.shutdown_timeout_secs);
+    match tokio::time::timeout(drain_timeout, &mut server).await {
+        Ok(Ok(Err(error))) => error!(%error, "server error during shutdown"),
+        Ok(Err(error)) => error!(%error, "server task failed during shutdown"),
+        Ok(Ok(Ok(()))) => {}
+        Err(_) => warn!(
+            timeout_secs = drain_timeout.as_secs(),
+            "in-flight requests did not finish in time; closing remaining connections"
+        ),
+    }
+
+    if let Some(metrics_server) = metrics_server {
+        match metrics_server.await {
+            Ok(Err(error)) => error!(%error, "metrics server error during shutdown"),
+            Err(error) => error!(%error, "metrics server task failed during shutdown"),
+            Ok(Ok(())) => {}
+        }
+    }
+
+    if let Err(error) = generator.await {
+        error!(%error, "transmission generator failed");
+    }
+
+    let snapshot = state.transmissions.read().await;
+    if let Err(error) = state.store.save(&snapshot) {
+        METRICS.persistence_failed();
+        error!(%error, "failed to persist transmissions on shutdown");
+    }
+}
+
+/// Every request runs in a span carrying its method, path and `x-request-id`,
+/// which is generated when the client did not send one and echoed back.
+fn request_span(request: &axum::extract::Request) -> Span {
+    let request_id = request
+        .headers()
+        .get("x-request-id")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    info_span!(
+        "request",
+        method = %request.method(),
+        path = %request.uri().path(),
+        request_id,
+    )
+}
+
+fn log_response(response: &Response, latency: Duration, _span: &Span) {
+    info!(
+        status = response.status().as_u16(),
+        latency_us = latency.as_micros() as u64,
+        "request completed"
+    );
+}
+
+/// Records request counts and latency under the matched route pattern, so
+/// every `/transmissions/{timestamp}` shares one series.
+async fn track_request_metrics(request: axum::extract::Request, next: Next) -> Response {
+    let method = request.method().clone();
+    // Nested services such as `/static` do not record a matched path.
+    let route = match request.extensions().get::<MatchedPath>() {
+        Some(path) => path.as_str().to_string(),
+        None if request.uri().path().starts_with("/static/") => "/static".to_string(),
+        None => "unmatched".to_string(),
+    };
+    let started_at = Instant::now();
+    let response = next.run(request).await;
+    METRICS.record_request(
+        method.as_str(),
+        &route,
+        response.status().as_u16(),
+        started_at.elapsed(),
+    );
+    response
+}
+
+/// Adds caching headers and answers conditional requests according to
+/// `cache_policy`.
+async fn apply_cache_policy(
+    State(app_state): State<AppState>,
+    request: axum::extract::Request,
+    next: Next,
+) -> Response {
+    let route = request.extensions().get::<MatchedPath>().cloned();
+    let policy = cache_policy(
+        &app_state,
+        request.method().clone(),
+        request.uri().clone(),
+        route.as_ref().map(MatchedPath::as_str),
+    )
+    .await;
+    let request_headers = request.headers().clone();
+    let response = next.run(request).await;
+    match policy {
+        Some(policy) => http_cache::apply(policy, &request_headers, response).await,
+        None => response,
+    }
+}
+
+/// Routes without a policy, such as the live transmission stream, redirects
+/// and the 404 page, are sent unchanged.
+async fn cache_policy(
+    app_state: &AppState,
+    method: axum::http::Method,
+    uri: axum::http::Uri,
+    route: Option<&str>,
+) -> Option<CachePolicy> {
+    if method != axum::http::Method::GET && method != axum::http::Method::HEAD {
+        return route.map(|_| CachePolicy::NoStore);
+    }
+
+    if let Some(path) = uri.path().strip_prefix("/static/") {
+        let versioned = uri.query().and_then(|query| {
+            query
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("v="))
+                .map(str::to_string)
+        });
+        return Some(CachePolicy::StaticAsset {
+            path: path.to_string(),
+            versioned,
+        });
+    }
+
+    let route = route?;
+    let route = match route.strip_suffix('/') {
+        Some(route) if !route.is_empty() => route,
+        _ => route,
+    };
+    // Only bodies built from a single source of known age get a
+    // `Last-Modified`. Pages also depend on templates, asset fingerprints and
+    // relative times, so they are revalidated by their ETag alone.
+    match route {
+        "/transmissions.atom" => Some(CachePolicy::Revalidate {
+            max_age: TRANSMISSION_PAGES_MAX_AGE_SECS,
+            last_modified: Some(app_state.transmissions.read().await.modified_at),
+        }),
+        "/" | "/transmissions" | "/transmissions/{timestamp}" | "/api/transmissions" => {
+            Some(CachePolicy::Revalidate {
+                max_age: TRANSMISSION_PAGES_MAX_AGE_SECS,
+                last_modified: None,
+            })
+        }
+        route
+            if route.starts_with("/api/ethereal-waves")
+                || matches!(
+                    route,
+                    "/ethereal-waves/changelog.atom" | "/ethereal-waves/changelog.rss"
+                ) =>
+        {
+            Some(CachePolicy::Revalidate {
+                max_age: RELEASE_PAGES_MAX_AGE_SECS,
+                last_modified: Some(release_notes_modified_at(
+                    &app_state.config.release_notes_path,
+                )),
+            })
+        }
+        route if route.starts_with("/ethereal-waves") => Some(CachePolicy::Revalidate {
+            max_age: RELEASE_PAGES_MAX_AGE_SECS,
+            last_modified: None,
+        }),
+        "/robots.txt" | "/sitemap.xml" => Some(CachePolicy::Revalidate {
+            max_age: SITE_FILES_MAX_AGE_SECS,
+            last_modified: None,
+        }),
+        "/healthz" | "/readyz" | "/metrics" => Some(CachePolicy::NoStore),
+        _ => None,
+    }
+}
+
+async fn metrics_handler() -> impl IntoResponse {
+    (
+        [(
+            header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )],
+        METRICS.render(),
+    )
+}
+
+/// Serves only `/metrics` on `address`, for scrapers that should not reach
+/// the site itself.
+async fn serve_metrics(
+    address: std::net::SocketAddr,
+    mut shutdown: watch::Receiver<bool>,
+) -> JoinHandle<std::io::Result<()>> {
+    let app = Router::new()
+        .route("/metrics", get(metrics_handler))
+        .route("/metrics/", get(metrics_handler));
+    let listener = tokio::net::TcpListener::bind(address)
+        .await
+        .expect("failed to bind metrics address");
+    info!(%address, "serving metrics");
+
+    tokio::spawn(
+        axum::serve(listener, app)
+            .with_graceful_shutdown(async move {
+                let _ = shutdown.changed().await;
+            })
+            .into_future(),
+    )
+}
+
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(error) = tokio::signal::ctrl_c().await {
+            error!(%error, "failed to listen for SIGINT");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(error) => {
+                error!(%error, "failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = ctrl_c => {}
+        () = terminate => {}
+    }
 }
 
 async fn index(State(app_state): State<AppState>) -> impl IntoResponse {
-    generate_if_needed_and_persist(&app_state).await;
+    HtmlTemplate(index_page(&app_state).await)
+}
+
+async fn index_page(app_state: &AppState) -> IndexTemplate {
     let recent_transmissions = {
         let guard = app_state.transmissions.read().await;
-        guard.entries.iter().take(5).cloned().collect()
+        transmission_views(guard.entries.iter().take(5).cloned(), app_state)
     };
-    let canonical_url = absolute_url(&app_state.site_url, "/");
-    let og_image_url = absolute_url(&app_state.site_url, HOME_OG_IMAGE_PATH);
+    let canonical_url = absolute_url(&app_state.config.site_url, "/");
+    let og_image_url = absolute_url(&app_state.config.site_url, HOME_OG_IMAGE_PATH);
 
-    HtmlTemplate(IndexTemplate {
+    IndexTemplate {
         title: "Galactic Pirate Radio | Home of Ethereal Waves",
         description: "Galactic Pirate Radio is the home of Ethereal Waves, a Linux music player for local audio files built with libcosmic and GStreamer.",
         current_path: "/",
@@ -101,14 +623,549 @@ async fn index(State(app_state): State<AppState>) -> impl IntoResponse {
         og_image_url,
         og_type: "website",
         robots: "index,follow",
-        site_url: app_state.site_url.clone(),
+        site_url: app_state.config.site_url.clone(),
         recent_transmissions,
+    }
+}
+
+/// Liveness: answers as long as the process can serve requests at all.
+async fn healthz() -> Response {
+    Json(serde_json::json!({ "status": "ok" })).into_response()
+}
+
+#[derive(Serialize)]
+struct ReadinessCheck {
+    ok: bool,
+    detail: String,
+}
+
+impl ReadinessCheck {
+    fn from_result(result: Result<String, String>) -> Self {
+        match result {
+            Ok(detail) => Self { ok: true, detail },
+            Err(detail) => Self { ok: false, detail },
+        }
+    }
+}
+
+/// Readiness: checks everything a page view depends on without generating
+/// transmissions, and answers 503 with the failing checks when any fails.
+async fn readyz(State(app_state): State<AppState>) -> Response {
+    let templates = index_page(&app_state)
+        .await
+        .render()
+        .map(|_| "home page renders".to_string())
+        .map_err(|error| format!("home page failed to render: {error}"));
+
+    let static_dir = &app_state.config.static_dir;
+    let static_files = fs::read_dir(static_dir)
+        .map(|_| format!("{} is readable", static_dir.display()))
+        .map_err(|error| format!("{}: {error}", static_dir.display()));
+
+    let releases = load_release_notes(&app_state).await.len();
+    let release_notes = match releases {
+        0 => Err("no releases parsed from the release notes".to_string()),
+        releases => Ok(format!("{releases} releases")),
+    };
+
+    let store = app_state
+        .store
+        .check_writable()
+        .map(|()| "writable".to_string())
+        .map_err(|error| format!("not writable: {error}"));
+
+    let heartbeat = METRICS.last_generator_heartbeat();
+    let since_heartbeat = unix_now_secs().saturating_sub(heartbeat);
+    let generator = if heartbeat == 0 {
+        Err("has not run yet".to_string())
+    } else if since_heartbeat > GENERATOR_TICK_SECS * GENERATOR_MISSED_TICKS_UNREADY {
+        Err(format!("last ran {since_heartbeat} s ago"))
+    } else {
+        Ok(format!("last ran {since_heartbeat} s ago"))
+    };
+
+    let checks = std::collections::BTreeMap::from([
+        ("templates", ReadinessCheck::from_result(templates)),
+        ("static_dir", ReadinessCheck::from_result(static_files)),
+        ("release_notes", ReadinessCheck::from_result(release_notes)),
+        ("transmission_store", ReadinessCheck::from_result(store)),
+        ("generator", ReadinessCheck::from_result(generator)),
+    ]);
+    let ready = checks.values().all(|check| check.ok);
+    if !ready {
+        let failing: Vec<_> = checks
+            .iter()
+            .filter(|(_, check)| !check.ok)
+            .map(|(name, _)| *name)
+            .collect();
+        warn!(?failing, "readiness check failed");
+    }
+
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    let body = serde_json::json!({
+        "status": if ready { "ready" } else { "not ready" },
+        "checks": checks,
+    });
+    (status, Json(body)).into_response()
+}
+
+#[derive(Deserialize)]
+struct ArchiveQuery {
+    page: Option<usize>,
+}
+
+async fn transmissions_archive(
+    State(app_state): State<AppState>,
+    Query(query): Query<ArchiveQuery>,
+) -> Response {
+    let page = query.page.unwrap_or(1).max(1);
+    let total = match app_state.store.count() {
+        Ok(total) => total,
+        Err(error) => return store_error_response(error),
+    };
+    let total_pages = total.div_ceil(TRANSMISSIONS_PER_PAGE).max(1);
+    if page > total_pages {
+        return not_found(State(app_state)).await.into_response();
+    }
+
+    let entries = match app_state
+        .store
+        .list((page - 1) * TRANSMISSIONS_PER_PAGE, TRANSMISSIONS_PER_PAGE)
+    {
+        Ok(entries) => transmission_views(entries, &app_state),
+        Err(error) => return store_error_response(error),
+    };
+    let canonical_path = if page == 1 {
+        "/transmissions".to_string()
+    } else {
+        format!("/transmissions?page={page}")
+    };
+    let canonical_url = absolute_url(&app_state.config.site_url, &canonical_path);
+    let og_image_url = absolute_url(&app_state.config.site_url, HOME_OG_IMAGE_PATH);
+
+    HtmlTemplate(TransmissionsTemplate {
+        title: if page == 1 {
+            "Transmission Archive | Galactic Pirate Radio".to_string()
+        } else {
+            format!("Transmission Archive, Page {page} | Galactic Pirate Radio")
+        },
+        description: "The full Galactic Pirate Radio station log, newest transmissions first.",
+        current_path: "/transmissions",
+        current_year: current_year(),
+        canonical_url,
+        og_image_url,
+        og_type: "website",
+        robots: "index,follow",
+        site_url: app_state.config.site_url.clone(),
+        entries,
+        page,
+        total_pages,
+        newer_page_url: match page {
+            1 => None,
+            2 => Some("/transmissions".to_string()),
+            _ => Some(format!("/transmissions?page={}", page - 1)),
+        },
+        older_page_url: (page < total_pages).then(|| format!("/transmissions?page={}", page + 1)),
+    })
+    .into_response()
+}
+
+async fn transmission_permalink(
+    State(app_state): State<AppState>,
+    UrlPath(timestamp): UrlPath<String>,
+) -> Response {
+    let Ok(timestamp) = timestamp.parse::<u64>() else {
+        return not_found(State(app_state)).await.into_response();
+    };
+    let entry = match app_state.store.get(timestamp) {
+        Ok(Some(entry)) => {
+            TransmissionView::new(entry, app_state.config.display_timezone, unix_now_secs())
+        }
+        Ok(None) => return not_found(State(app_state)).await.into_response(),
+        Err(error) => return store_error_response(error),
+    };
+    let (older_timestamp, newer_timestamp) = match app_state.store.adjacent(timestamp) {
+        Ok(adjacent) => adjacent,
+        Err(error) => return store_error_response(error),
+    };
+    let canonical_url = absolute_url(
+        &app_state.config.site_url,
+        &format!("/transmissions/{timestamp}"),
+    );
+    let og_image_url = absolute_url(&app_state.config.site_url, HOME_OG_IMAGE_PATH);
+
+    HtmlTemplate(TransmissionTemplate {
+        title: format!("Transmission {} | Galactic Pirate Radio", entry.time_label),
+        description: entry.entry.message.clone(),
+        current_path: "/transmissions",
+        current_year: current_year(),
+        canonical_url,
+        og_image_url,
+        og_type: "article",
+        robots: "index,follow",
+        site_url: app_state.config.site_url.clone(),
+        entry,
+        older_timestamp,
+        newer_timestamp,
     })
+    .into_response()
+}
+
+async fn transmissions_atom(State(app_state): State<AppState>) -> Response {
+    let entries = match app_state.store.list(0, TRANSMISSIONS_PER_PAGE) {
+        Ok(entries) => entries,
+        Err(error) => return store_error_response(error),
+    };
+    let feed_url = absolute_url(&app_state.config.site_url, "/transmissions.atom");
+    let updated_at = entries
+        .first()
+        .map(|entry| entry.timestamp)
+        .unwrap_or_else(unix_now_secs);
+    let body =
+        feeds::transmissions_atom(&app_state.config.site_url, &feed_url, updated_at, &entries);
+    (
+        [(header::CONTENT_TYPE, "application/atom+xml; charset=utf-8")],
+        body,
+    )
+        .into_response()
+}
+
+#[derive(Deserialize)]
+struct TransmissionsApiQuery {
+    limit: Option<usize>,
+    since: Option<u64>,
+}
+
+/// Returns transmissions newest first. With `since`, only entries newer than
+/// that timestamp are returned, starting from the oldest of them, so a client
+/// polling with its newest seen timestamp never skips entries.
+async fn api_transmissions(
+    State(app_state): State<AppState>,
+    Query(query): Query<TransmissionsApiQuery>,
+) -> Response {
+    let limit = query
+        .limit
+        .unwrap_or(TRANSMISSIONS_API_DEFAULT_LIMIT)
+        .clamp(1, TRANSMISSIONS_API_MAX_LIMIT);
+    let entries = match query.since {
+        Some(since) => app_state.store.after(since, limit).map(|mut entries| {
+            entries.reverse();
+            entries
+        }),
+        None => app_state.store.list(0, limit),
+    };
+
+    match entries {
+        Ok(entries) => Json(transmission_views(entries, &app_state)).into_response(),
+        Err(error) => store_error_response(error),
+    }
+}
+
+/// Pushes each newly generated transmission as a `transmission` event whose
+/// ID is its timestamp. Reconnecting clients send that ID back as
+/// `Last-Event-ID` and first receive whatever they missed from the store.
+async fn transmissions_stream(
+    State(app_state): State<AppState>,
+    headers: HeaderMap,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    // Subscribe before reading the store so nothing generated in between is
+    // lost; duplicates are dropped by comparing timestamps below.
+    let receiver = app_state.transmission_events.subscribe();
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok());
+
+    let replay = match last_event_id {
+        Some(last_event_id) => app_state
+            .store
+            .after(last_event_id, TRANSMISSIONS_API_MAX_LIMIT)
+            .unwrap_or_else(|error| {
+                error!(last_event_id, %error, "failed to replay transmissions");
+                Vec::new()
+            }),
+        None => Vec::new(),
+    };
+    let last_sent = replay
+        .last()
+        .map(|entry| entry.timestamp)
+        .or(last_event_id)
+        .unwrap_or(0);
+
+    let timezone = app_state.config.display_timezone;
+    let replay = stream::iter(replay).map(move |entry| Ok(transmission_event(entry, timezone)));
+    let store = app_state.store.clone();
+    let live = stream::unfold(
+        (
+            receiver,
+            app_state.shutdown,
+            last_sent,
+            Vec::<TransmissionEntry>::new().into_iter(),
+        ),
+        move |(mut receiver, mut shutdown, last_sent, mut missed)| {
+            let store = store.clone();
+            async move {
+                loop {
+                    if *shutdown.borrow() {
+                        return None;
+                    }
+                    // Entries the receiver lagged past are sent from the store
+                    // before listening again.
+                    if let Some(entry) = missed.next() {
+                        if entry.timestamp > last_sent {
+                            let timestamp = entry.timestamp;
+                            let event = Ok(transmission_event(entry, timezone));
+                            return Some((event, (receiver, shutdown, timestamp, missed)));
+                        }
+                        continue;
+                    }
+                    tokio::select! {
+                        received = receiver.recv() => match received {
+                            Ok(entry) if entry.timestamp > last_sent => {
+                                let timestamp = entry.timestamp;
+                                let event = Ok(transmission_event(entry, timezone));
+                                return Some((event, (receiver, shutdown, timestamp, missed)));
+                            }
+                            Ok(_) => {}
+                            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                                warn!(
+                                    skipped,
+                                    "transmission stream lagged; replaying from the store"
+                                );
+                                missed = store
+                                    .after(last_sent, TRANSMISSIONS_API_MAX_LIMIT)
+                                    .unwrap_or_else(|error| {
+                                        error!(
+                                            last_sent,
+                                            %error,
+                                            "failed to replay transmissions"
+                                        );
+                                        Vec::new()
+                                    })
+                                    .into_iter();
+                            }
+                            Err(broadcast::error::RecvError::Closed) => return None,
+                        },
+                        changed = shutdown.changed() => {
+                            if changed.is_err() {
+                                return None;
+                            }
+                        }
+                    }
+                }
+            }
+        },
+    );
+
+    Sse::new(replay.chain(live)).keep_alive(KeepAlive::default())
+}
+
+fn transmission_event(entry: TransmissionEntry, timezone: UtcOffset) -> Event {
+    let timestamp = entry.timestamp;
+    Event::default()
+        .event("transmission")
+        .id(timestamp.to_string())
+        .json_data(TransmissionView::new(entry, timezone, unix_now_secs()))
+        .unwrap_or_else(|error| {
+            error!(transmission = timestamp, %error, "failed to encode transmission");
+            Event::default().comment("encoding error")
+        })
+}
+
+#[derive(Deserialize)]
+struct ManualTransmission {
+    message: String,
+    link: Option<String>,
+    /// Restart the generation interval from this entry, delaying the next
+    /// generated transmission.
+    #[serde(default)]
+    reset_interval: bool,
+}
+
+async fn admin_post_transmission(
+    State(app_state): State<AppState>,
+    Json(request): Json<ManualTransmission>,
+) -> Response {
+    let message = request.message.trim().to_string();
+    if message.is_empty() || message.chars().count() > MAX_MANUAL_MESSAGE_CHARS {
+        return admin_error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            format!("message must be between 1 and {MAX_MANUAL_MESSAGE_CHARS} characters"),
+        );
+    }
+    let link = request
+        .link
+        .map(|link| link.trim().to_string())
+        .filter(|link| !link.is_empty());
+    if let Some(link) = &link
+        && !is_manual_transmission_link(link)
+    {
+        return admin_error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "link must be a site path or an http(s) URL".to_string(),
+        );
+    }
+
+    let (saved, entry) = {
+        let mut guard = app_state.transmissions.write().await;
+        let Some(entry) = push_transmission(
+            &mut guard,
+            unix_now_secs(),
+            message,
+            TransmissionKind::Manual,
+            link,
+        ) else {
+            return admin_error(
+                StatusCode::CONFLICT,
+                "the clock is behind the newest transmissions; try again later".to_string(),
+            );
+        };
+        guard.modified_at = entry.timestamp;
+        if request.reset_interval {
+            guard.last_generated_at = entry.timestamp;
+        }
+        // Saved under the lock, so saves reach the store in the order the
+        // changes were made.
+        (app_state.store.save(&guard), entry)
+    };
+
+    if let Err(error) = saved {
+        METRICS.persistence_failed();
+        return store_error_response(error);
+    }
+    METRICS.transmission_logged(entry.kind.as_str());
+    info!(transmission = entry.timestamp, "posted manual transmission");
+    let _ = app_state.transmission_events.send(entry.clone());
+
+    (
+        StatusCode::CREATED,
+        [(
+            header::LOCATION,
+            format!("/transmissions/{}", entry.timestamp),
+        )],
+        Json(TransmissionView::new(
+            entry,
+            app_state.config.display_timezone,
+            unix_now_secs(),
+        )),
+    )
+        .into_response()
+}
+
+/// Manual transmissions may link to a page on this site or to a web URL.
+fn is_manual_transmission_link(link: &str) -> bool {
+    markdown::is_safe_url(link)
+        && (link.starts_with('/') || link.starts_with("https://") || link.starts_with("http://"))
+}
+
+async fn admin_delete_transmission(
+    State(app_state): State<AppState>,
+    UrlPath(timestamp): UrlPath<String>,
+) -> Response {
+    let Ok(timestamp) = timestamp.parse::<u64>() else {
+        return admin_error(
+            StatusCode::NOT_FOUND,
+            format!("no transmission at {timestamp}"),
+        );
+    };
+
+    // Every save happens under this lock, so a generation that ran before
+    // the delete cannot save the entry back after it.
+    let mut guard = app_state.transmissions.write().await;
+    guard.entries.retain(|entry| entry.timestamp != timestamp);
+    match app_state.store.delete(timestamp) {
+        Ok(true) => {
+            guard.modified_at = unix_now_secs();
+            info!(transmission = timestamp, "deleted transmission");
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Ok(false) => admin_error(
+            StatusCode::NOT_FOUND,
+            format!("no transmission at {timestamp}"),
+        ),
+        Err(error) => {
+            METRICS.persistence_failed();
+            store_error_response(error)
+        }
+    }
+}
+
+/// Guards the `/admin` routes. It runs before their extractors, so a request
+/// without a valid token is turned away before its body is parsed.
+async fn require_admin_token(
+    State(app_state): State<AppState>,
+    request: axum::extract::Request,
+    next: Next,
+) -> Response {
+    match reject_unauthorized_admin(&app_state, request.headers()) {
+        Some(response) => response,
+        None => next.run(request).await,
+    }
+}
+
+/// Returns an error response unless the request carries
+/// `Authorization: Bearer <admin_token>`. The admin endpoints answer 404 when
+/// no token is configured.
+fn reject_unauthorized_admin(app_state: &AppState, headers: &HeaderMap) -> Option<Response> {
+    let Some(expected) = &app_state.config.admin_token else {
+        return Some(admin_error(
+            StatusCode::NOT_FOUND,
+            "admin endpoints are disabled".to_string(),
+        ));
+    };
+    let provided = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim);
+
+    match provided {
+        Some(provided) if constant_time_eq(provided.as_bytes(), expected.trim().as_bytes()) => None,
+        _ => {
+            let mut response = admin_error(
+                StatusCode::UNAUTHORIZED,
+                "missing or invalid bearer token".to_string(),
+            );
+            response.headers_mut().insert(
+                header::WWW_AUTHENTICATE,
+                header::HeaderValue::from_static("Bearer"),
+            );
+            Some(response)
+        }
+    }
+}
+
+/// Compares without exiting early, so response timing does not reveal how
+/// much of the token matched.
+fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    left.len() == right.len()
+        && left
+            .iter()
+            .zip(right)
+            .fold(0u8, |difference, (left, right)| difference | (left ^ right))
+            == 0
+}
+
+fn admin_error(status: StatusCode, message: String) -> Response {
+    (status, Json(serde_json::json!({ "error": message }))).into_response()
+}
+
+fn store_error_response(error: std::io::Error) -> Response {
+    error!(%error, "transmission store error");
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "transmission archive is temporarily unavailable",
+    )
+        .into_response()
 }
 
 async fn ethereal_waves(State(app_state): State<AppState>) -> impl IntoResponse {
-    let canonical_url = absolute_url(&app_state.site_url, "/ethereal-waves");
-    let og_image_url = absolute_url(&app_state.site_url, ETHEREAL_WAVES_OG_IMAGE_PATH);
+    let canonical_url = absolute_url(&app_state.config.site_url, "/ethereal-waves");
+    let og_image_url = absolute_url(&app_state.config.site_url, ETHEREAL_WAVES_OG_IMAGE_PATH);
 
     HtmlTemplate(EtherealWavesTemplate {
         title: "Ethereal Waves Music Player for Linux | Galactic Pirate Radio",
@@ -119,14 +1176,14 @@ async fn ethereal_waves(State(app_state): State<AppState>) -> impl IntoResponse
         og_image_url,
         og_type: "website",
         robots: "index,follow",
-        site_url: app_state.site_url.clone(),
+        site_url: app_state.config.site_url.clone(),
     })
 }
 
 async fn ethereal_waves_changelog(State(app_state): State<AppState>) -> impl IntoResponse {
-    let canonical_url = absolute_url(&app_state.site_url, "/ethereal-waves/changelog");
-    let og_image_url = absolute_url(&app_state.site_url, ETHEREAL_WAVES_OG_IMAGE_PATH);
-    let release_entries = load_release_notes();
+    let canonical_url = absolute_url(&app_state.config.site_url, "/ethereal-waves/changelog");
+    let og_image_url = absolute_url(&app_state.config.site_url, ETHEREAL_WAVES_OG_IMAGE_PATH);
+    let release_entries = load_release_notes(&app_state).await;
     let release_notes_available = !release_entries.is_empty();
 
     HtmlTemplate(EtherealWavesChangelogTemplate {
@@ -138,19 +1195,221 @@ async fn ethereal_waves_changelog(State(app_state): State<AppState>) -> impl Int
         og_image_url,
         og_type: "article",
         robots: "index,follow",
-        site_url: app_state.site_url.clone(),
+        site_url: app_state.config.site_url.clone(),
         release_notes_available,
         release_entries,
     })
 }
 
+async fn ethereal_waves_release(
+    State(app_state): State<AppState>,
+    UrlPath(version): UrlPath<String>,
+) -> Response {
+    let version = strip_version_prefix(&version);
+    let release_entries = load_release_notes(&app_state).await;
+    let Some(position) = release_entries
+        .iter()
+        .position(|entry| entry.version == version)
+    else {
+        return not_found(State(app_state)).await.into_response();
+    };
+
+    let release = release_entries[position].clone();
+    let newer_version = position
+        .checked_sub(1)
+        .map(|index| release_entries[index].version.clone());
+    let older_version = release_entries
+        .get(position + 1)
+        .map(|entry| entry.version.clone());
+    let canonical_url = absolute_url(
+        &app_state.config.site_url,
+        &format!("/ethereal-waves/changelog/{}", release.version),
+    );
+    let og_image_url = absolute_url(&app_state.config.site_url, ETHEREAL_WAVES_OG_IMAGE_PATH);
+    let description = match release
+        .sections
+        .first()
+        .and_then(|section| section.items.first())
+    {
+        Some(item) => format!(
+            "What changed in Ethereal Waves {}: {}",
+            release.version, item.text
+        ),
+        None => format!("Release notes for Ethereal Waves {}.", release.version),
+    };
+
+    HtmlTemplate(EtherealWavesReleaseTemplate {
+        title: format!(
+            "Ethereal Waves {} Release Notes | Galactic Pirate Radio",
+            release.version
+        ),
+        description,
+        current_path: "/ethereal-waves",
+        current_year: current_year(),
+        canonical_url,
+        og_image_url,
+        og_type: "article",
+        robots: "index,follow",
+        site_url: app_state.config.site_url.clone(),
+        release,
+        newer_version,
+        older_version,
+    })
+    .into_response()
+}
+
+#[derive(Deserialize)]
+struct CompareQuery {
+    from: Option<String>,
+    to: Option<String>,
+}
+
+async fn ethereal_waves_changelog_compare(
+    State(app_state): State<AppState>,
+    Query(query): Query<CompareQuery>,
+) -> Response {
+    let release_entries = load_release_notes(&app_state).await;
+    let from = query.from.filter(|value| !value.trim().is_empty());
+    let to = query.to.filter(|value| !value.trim().is_empty());
+    let canonical_path = match (&from, &to) {
+        (Some(from), Some(to)) => format!(
+            "/ethereal-waves/changelog/compare?from={}&to={}",
+            strip_version_prefix(from.trim()),
+            strip_version_prefix(to.trim())
+        ),
+        _ => "/ethereal-waves/changelog/compare".to_string(),
+    };
+
+    let mut status = StatusCode::OK;
+    let mut error = None;
+    let mut comparison = None;
+    match (&from, &to) {
+        (Some(from), Some(to)) => {
+            match (SemanticVersion::parse(from), SemanticVersion::parse(to)) {
+                (Some(from_version), Some(to_version)) => {
+                    let (lower, upper) = if from_version <= to_version {
+                        (from_version, to_version)
+                    } else {
+                        (to_version, from_version)
+                    };
+                    comparison = Some(compare_release_entries(&release_entries, &lower, &upper));
+                }
+                _ => {
+                    status = StatusCode::BAD_REQUEST;
+                    error = Some(format!(
+                        "Could not compare \"{}\" with \"{}\". Use versions like 0.7.0 and 0.9.0.",
+                        from.trim(),
+                        to.trim()
+                    ));
+                }
+            }
+        }
+        (None, None) => {}
+        _ => {
+            status = StatusCode::BAD_REQUEST;
+            error = Some("Choose both a starting and an ending version.".to_string());
+        }
+    }
+
+    let canonical_url = absolute_url(&app_state.config.site_url, &canonical_path);
+    let og_image_url = absolute_url(&app_state.config.site_url, ETHEREAL_WAVES_OG_IMAGE_PATH);
+    let versions = release_entries
+        .iter()
+        .map(|entry| entry.version.clone())
+        .collect();
+    let from = from
+        .map(|value| strip_version_prefix(value.trim()).to_string())
+        .or_else(|| release_entries.last().map(|entry| entry.version.clone()))
+        .unwrap_or_default();
+    let to = to
+        .map(|value| strip_version_prefix(value.trim()).to_string())
+        .or_else(|| release_entries.first().map(|entry| entry.version.clone()))
+        .unwrap_or_default();
+
+    (
+        status,
+        HtmlTemplate(EtherealWavesCompareTemplate {
+            title: "Compare Ethereal Waves Releases | Galactic Pirate Radio",
+            description: "Combined summary of Ethereal Waves changes between two releases.",
+            current_path: "/ethereal-waves",
+            current_year: current_year(),
+            canonical_url,
+            og_image_url,
+            og_type: "website",
+            robots: "noindex,follow",
+            site_url: app_state.config.site_url.clone(),
+            versions,
+            from,
+            to,
+            error,
+            comparison,
+        }),
+    )
+        .into_response()
+}
+
+async fn ethereal_waves_changelog_atom(State(app_state): State<AppState>) -> impl IntoResponse {
+    let changelog_url = absolute_url(&app_state.config.site_url, "/ethereal-waves/changelog");
+    let feed_url = absolute_url(&app_state.config.site_url, "/ethereal-waves/changelog.atom");
+    let body = feeds::release_notes_atom(
+        &changelog_url,
+        &feed_url,
+        release_notes_modified_at(&app_state.config.release_notes_path),
+        &load_release_notes(&app_state).await,
+    );
+    (
+        [(header::CONTENT_TYPE, "application/atom+xml; charset=utf-8")],
+        body,
+    )
+}
+
+async fn ethereal_waves_changelog_rss(State(app_state): State<AppState>) -> impl IntoResponse {
+    let changelog_url = absolute_url(&app_state.config.site_url, "/ethereal-waves/changelog");
+    let feed_url = absolute_url(&app_state.config.site_url, "/ethereal-waves/changelog.rss");
+    let body = feeds::release_notes_rss(
+        &changelog_url,
+        &feed_url,
+        release_notes_modified_at(&app_state.config.release_notes_path),
+        &load_release_notes(&app_state).await,
+    );
+    (
+        [(header::CONTENT_TYPE, "application/rss+xml; charset=utf-8")],
+        body,
+    )
+}
+
+async fn api_releases(State(app_state): State<AppState>) -> Response {
+    Json(load_release_notes(&app_state).await.as_slice()).into_response()
+}
+
+async fn api_release(
+    State(app_state): State<AppState>,
+    UrlPath(version): UrlPath<String>,
+) -> Response {
+    let version = strip_version_prefix(&version);
+    match load_release_notes(&app_state)
+        .await
+        .iter()
+        .find(|entry| entry.version == version)
+    {
+        Some(entry) => Json(entry).into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({
+                "error": format!("unknown Ethereal Waves version: {version}"),
+            })),
+        )
+            .into_response(),
+    }
+}
+
 async fn legacy_software_redirect() -> impl IntoResponse {
     Redirect::permanent("/ethereal-waves")
 }
 
 async fn not_found(State(app_state): State<AppState>) -> impl IntoResponse {
-    let canonical_url = absolute_url(&app_state.site_url, "/404");
-    let og_image_url = absolute_url(&app_state.site_url, HOME_OG_IMAGE_PATH);
+    let canonical_url = absolute_url(&app_state.config.site_url, "/404");
+    let og_image_url = absolute_url(&app_state.config.site_url, HOME_OG_IMAGE_PATH);
     (
         StatusCode::NOT_FOUND,
         HtmlTemplate(NotFoundTemplate {
@@ -162,7 +1421,7 @@ async fn not_found(State(app_state): State<AppState>) -> impl IntoResponse {
             og_image_url,
             og_type: "website",
             robots: "noindex,follow",
-            site_url: app_state.site_url,
+            site_url: app_state.config.site_url.clone(),
         }),
     )
 }
@@ -170,15 +1429,41 @@ async fn not_found(State(app_state): State<AppState>) -> impl IntoResponse {
 async fn robots_txt(State(app_state): State<AppState>) -> impl IntoResponse {
     let body = format!(
         "User-agent: *\nAllow: /\nSitemap: {}/sitemap.xml\n",
-        app_state.site_url
+        app_state.config.site_url
     );
     ([(header::CONTENT_TYPE, "text/plain; charset=utf-8")], body)
 }
 
 async fn sitemap_xml(State(app_state): State<AppState>) -> impl IntoResponse {
-    let home = absolute_url(&app_state.site_url, "/");
-    let ethereal_waves = absolute_url(&app_state.site_url, "/ethereal-waves");
-    let changelog = absolute_url(&app_state.site_url, "/ethereal-waves/changelog");
+    let home = absolute_url(&app_state.config.site_url, "/");
+    let ethereal_waves = absolute_url(&app_state.config.site_url, "/ethereal-waves");
+    let changelog = absolute_url(&app_state.config.site_url, "/ethereal-waves/changelog");
+    let transmissions = absolute_url(&app_state.config.site_url, "/transmissions");
+    let transmissions_atom = absolute_url(&app_state.config.site_url, "/transmissions.atom");
+    let changelog_atom = absolute_url(&app_state.config.site_url, "/ethereal-waves/changelog.atom");
+    let changelog_rss = absolute_url(&app_state.config.site_url, "/ethereal-waves/changelog.rss");
+    let release_entries = load_release_notes(&app_state).await;
+    let changelog_lastmod = release_entries
+        .iter()
+        .filter_map(|entry| entry.date.as_deref())
+        .max()
+        .map(|date| format!("\n    <lastmod>{date}</lastmod>"))
+        .unwrap_or_default();
+    let release_urls: String = release_entries
+        .iter()
+        .map(|entry| {
+            let loc = absolute_url(
+                &app_state.config.site_url,
+                &format!("/ethereal-waves/changelog/{}", entry.version),
+            );
+            let lastmod = entry
+                .date
+                .as_deref()
+                .map(|date| format!("\n    <lastmod>{date}</lastmod>"))
+                .unwrap_or_default();
+            format!("  <url>\n    <loc>{loc}</loc>{lastmod}\n  </url>\n")
+        })
+        .collect();
     let body = format!(
         r#"<?xml version="1.0" encoding="UTF-8"?>
 <urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
@@ -189,9 +1474,21 @@ async fn sitemap_xml(State(app_state): State<AppState>) -> impl IntoResponse {
     <loc>{ethereal_waves}</loc>
   </url>
   <url>
-    <loc>{changelog}</loc>
+    <loc>{changelog}</loc>{changelog_lastmod}
+  </url>
+  <url>
+    <loc>{transmissions}</loc>
   </url>
-</urlset>
+  <url>
+    <loc>{transmissions_atom}</loc>
+  </url>
+  <url>
+    <loc>{changelog_atom}</loc>{changelog_lastmod}
+  </url>
+  <url>
+    <loc>{changelog_rss}</loc>{changelog_lastmod}
+  </url>
+{release_urls}</urlset>
 "#
     );
     (
@@ -209,18 +1506,51 @@ where
     fn into_response(self) -> Response {
         match self.0.render() {
             Ok(html) => Html(html).into_response(),
-            Err(error) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("template render error: {error}"),
-            )
-                .into_response(),
+            Err(error) => {
+                METRICS.template_render_failed();
+                error!(template = std::any::type_name::<T>(), %error, "failed to render template");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("template render error: {error}"),
+                )
+                    .into_response()
+            }
         }
     }
 }
 
 #[derive(Template)]
-#[template(path = "index.html")]
-struct IndexTemplate {
+#[template(path = "index.html")]
+struct IndexTemplate {
+    title: &'static str,
+    description: &'static str,
+    current_path: &'static str,
+    current_year: i32,
+    canonical_url: String,
+    og_image_url: String,
+    og_type: &'static str,
+    robots: &'static str,
+    site_url: String,
+    recent_transmissions: Vec<TransmissionView>,
+}
+
+#[derive(Template)]
+#[template(path = "etherealwaves.html")]
+struct EtherealWavesTemplate {
+    title: &'static str,
+    description: &'static str,
+    current_path: &'static str,
+    current_year: i32,
+    canonical_url: String,
+    og_image_url: String,
+    og_type: &'static str,
+    robots: &'static str,
+    site_url: String,
+}
+
+#[derive(Template)]
+#[template(path = "ethereal_waves_changelog.html")]
+struct EtherealWavesChangelogTemplate {
     title: &'static str,
     description: &'static str,
     current_path: &'static str,
@@ -230,12 +1560,30 @@ struct IndexTemplate {
     og_type: &'static str,
     robots: &'static str,
     site_url: String,
-    recent_transmissions: Vec<TransmissionEntry>,
+    release_notes_available: bool,
+    release_entries: Arc<Vec<ReleaseEntry>>,
 }
 
 #[derive(Template)]
-#[template(path = "etherealwaves.html")]
-struct EtherealWavesTemplate {
+#[template(path = "ethereal_waves_release.html")]
+struct EtherealWavesReleaseTemplate {
+    title: String,
+    description: String,
+    current_path: &'static str,
+    current_year: i32,
+    canonical_url: String,
+    og_image_url: String,
+    og_type: &'static str,
+    robots: &'static str,
+    site_url: String,
+    release: ReleaseEntry,
+    newer_version: Option<String>,
+    older_version: Option<String>,
+}
+
+#[derive(Template)]
+#[template(path = "ethereal_waves_compare.html")]
+struct EtherealWavesCompareTemplate {
     title: &'static str,
     description: &'static str,
     current_path: &'static str,
@@ -245,12 +1593,17 @@ struct EtherealWavesTemplate {
     og_type: &'static str,
     robots: &'static str,
     site_url: String,
+    versions: Vec<String>,
+    from: String,
+    to: String,
+    error: Option<String>,
+    comparison: Option<ReleaseComparison>,
 }
 
 #[derive(Template)]
-#[template(path = "ethereal_waves_changelog.html")]
-struct EtherealWavesChangelogTemplate {
-    title: &'static str,
+#[template(path = "transmissions.html")]
+struct TransmissionsTemplate {
+    title: String,
     description: &'static str,
     current_path: &'static str,
     current_year: i32,
@@ -259,8 +1612,28 @@ struct EtherealWavesChangelogTemplate {
     og_type: &'static str,
     robots: &'static str,
     site_url: String,
-    release_notes_available: bool,
-    release_entries: Vec<ReleaseEntry>,
+    entries: Vec<TransmissionView>,
+    page: usize,
+    total_pages: usize,
+    newer_page_url: Option<String>,
+    older_page_url: Option<String>,
+}
+
+#[derive(Template)]
+#[template(path = "transmission.html")]
+struct TransmissionTemplate {
+    title: String,
+    description: String,
+    current_path: &'static str,
+    current_year: i32,
+    canonical_url: String,
+    og_image_url: String,
+    og_type: &'static str,
+    robots: &'static str,
+    site_url: String,
+    entry: TransmissionView,
+    older_timestamp: Option<u64>,
+    newer_timestamp: Option<u64>,
 }
 
 #[derive(Template)]
@@ -277,33 +1650,225 @@ struct NotFoundTemplate {
     site_url: String,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize)]
 struct ReleaseEntry {
     version: String,
     anchor_id: String,
+    date: Option<String>,
+    released_at: Option<u64>,
+    tag_url: Option<String>,
+    yanked: bool,
+    prerelease: bool,
     sections: Vec<ReleaseSection>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize)]
 struct ReleaseSection {
     title: String,
-    items: Vec<String>,
+    items: Vec<ReleaseItem>,
+}
+
+#[derive(Clone, Serialize)]
+struct ReleaseItem {
+    text: String,
+    html: String,
+    children: Vec<ReleaseItem>,
+}
+
+impl ReleaseItem {
+    fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            html: markdown::render_inline(text),
+            children: Vec::new(),
+        }
+    }
+
+    fn children_html(&self) -> String {
+        if self.children.is_empty() {
+            return String::new();
+        }
+
+        let mut html = String::from("<ul class=\"release-subitems\">");
+        for child in &self.children {
+            html.push_str(&format!("<li>{}{}</li>", child.html, child.children_html()));
+        }
+        html.push_str("</ul>");
+        html
+    }
+}
+
+#[derive(Default)]
+struct ReleaseNotesCache {
+    modified_at: Option<SystemTime>,
+    entries: Arc<Vec<ReleaseEntry>>,
+    /// Consecutive failed reads, which lengthen the wait before the next.
+    failed_reads: u32,
+    /// While the file is unreadable, the last good parse is served without
+    /// touching the file until this time.
+    retry_at: Option<Instant>,
 }
 
-fn load_release_notes() -> Vec<ReleaseEntry> {
-    match fs::read_to_string(RELEASE_NOTES_PATH) {
-        Ok(markdown) => parse_release_notes_markdown(&markdown),
+impl ReleaseNotesCache {
+    fn waiting_to_retry(&self, now: Instant) -> bool {
+        self.retry_at.is_some_and(|retry_at| now < retry_at)
+    }
+}
+
+#[instrument(name = "load_release_notes", skip_all)]
+async fn load_release_notes(app_state: &AppState) -> Arc<Vec<ReleaseEntry>> {
+    let path = &app_state.config.release_notes_path;
+    let modified_at;
+    {
+        let cache = app_state.release_notes.read().await;
+        if cache.waiting_to_retry(Instant::now()) {
+            return cache.entries.clone();
+        }
+        modified_at = fs::metadata(path).and_then(|metadata| metadata.modified());
+        if let Ok(modified_at) = &modified_at
+            && cache.modified_at == Some(*modified_at)
+        {
+            return cache.entries.clone();
+        }
+    }
+
+    let mut cache = app_state.release_notes.write().await;
+    // Another request may have retried while this one waited for the lock.
+    if cache.waiting_to_retry(Instant::now()) {
+        return cache.entries.clone();
+    }
+    let markdown = modified_at.and_then(|modified_at| {
+        if cache.modified_at == Some(modified_at) {
+            return Ok(None);
+        }
+        fs::read_to_string(path).map(|markdown| Some((modified_at, markdown)))
+    });
+
+    match markdown {
+        Ok(Some((modified_at, markdown))) => {
+            let entries = parse_release_notes_markdown(&markdown);
+            if entries.is_empty() {
+                METRICS.release_notes_parse_failed();
+            }
+            if entries.is_empty() && !cache.entries.is_empty() {
+                warn!(
+                    path = %path.display(),
+                    "release notes contain no entries; keeping last good parse"
+                );
+            } else {
+                info!(
+                    path = %path.display(),
+                    releases = entries.len(),
+                    "loaded release notes"
+                );
+                METRICS.release_notes_loaded(entries.len());
+                cache.entries = Arc::new(entries);
+            }
+            cache.modified_at = Some(modified_at);
+            cache.failed_reads = 0;
+            cache.retry_at = None;
+        }
+        Ok(None) => {}
         Err(error) => {
-            eprintln!("failed to read release notes from {RELEASE_NOTES_PATH}: {error}");
-            Vec::new()
+            let backoff = RELEASE_NOTES_RETRY_MIN_SECS
+                .saturating_mul(1 << cache.failed_reads.min(16))
+                .min(RELEASE_NOTES_RETRY_MAX_SECS);
+            cache.failed_reads = cache.failed_reads.saturating_add(1);
+            cache.retry_at = Some(Instant::now() + Duration::from_secs(backoff));
+            METRICS.release_notes_parse_failed();
+            error!(
+                path = %path.display(),
+                %error,
+                retry_in_secs = backoff,
+                "failed to read release notes; serving last good parse"
+            );
+        }
+    }
+
+    cache.entries.clone()
+}
+
+struct ReleaseComparison {
+    from: String,
+    to: String,
+    versions: Vec<String>,
+    sections: Vec<ComparedSection>,
+}
+
+struct ComparedSection {
+    title: String,
+    items: Vec<ComparedItem>,
+}
+
+struct ComparedItem {
+    version: String,
+    item: ReleaseItem,
+}
+
+/// Merges every release newer than `lower` up to and including `upper` into
+/// one list of sections keyed by section title.
+fn compare_release_entries(
+    entries: &[ReleaseEntry],
+    lower: &SemanticVersion,
+    upper: &SemanticVersion,
+) -> ReleaseComparison {
+    let mut versions = Vec::new();
+    let mut sections: Vec<ComparedSection> = Vec::new();
+
+    for entry in entries {
+        let Some(version) = SemanticVersion::parse(&entry.version) else {
+            continue;
+        };
+        if version <= *lower || version > *upper {
+            continue;
+        }
+
+        versions.push(entry.version.clone());
+        for section in &entry.sections {
+            let position = match sections
+                .iter()
+                .position(|compared| compared.title.eq_ignore_ascii_case(&section.title))
+            {
+                Some(position) => position,
+                None => {
+                    sections.push(ComparedSection {
+                        title: section.title.clone(),
+                        items: Vec::new(),
+                    });
+                    sections.len() - 1
+                }
+            };
+            sections[position]
+                .items
+                .extend(section.items.iter().map(|item| ComparedItem {
+                    version: entry.version.clone(),
+                    item: item.clone(),
+                }));
         }
     }
+
+    ReleaseComparison {
+        from: lower.to_string(),
+        to: upper.to_string(),
+        versions,
+        sections,
+    }
+}
+
+fn release_notes_modified_at(path: &Path) -> u64 {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs())
+        .unwrap_or_else(unix_now_secs)
 }
 
 fn parse_release_notes_markdown(markdown: &str) -> Vec<ReleaseEntry> {
     let mut entries = Vec::new();
     let mut current_entry: Option<ReleaseEntry> = None;
     let mut current_section: Option<ReleaseSection> = None;
+    let mut tag_urls: Vec<(String, String)> = Vec::new();
 
     for raw_line in markdown.lines() {
         let line = raw_line.trim();
@@ -311,33 +1876,24 @@ fn parse_release_notes_markdown(markdown: &str) -> Vec<ReleaseEntry> {
             continue;
         }
 
-        if let Some(version) = line.strip_prefix("## ") {
-            if let Some(section) = current_section.take() {
-                if !section.items.is_empty() {
-                    if let Some(entry) = current_entry.as_mut() {
-                        entry.sections.push(section);
-                    }
-                }
+        if let Some(heading) = line.strip_prefix("## ") {
+            if let Some(section) = current_section.take()
+                && !section.items.is_empty()
+                && let Some(entry) = current_entry.as_mut()
+            {
+                entry.sections.push(section);
             }
 
-            if let Some(entry) = current_entry.take() {
-                if !entry.sections.is_empty() {
-                    entries.push(entry);
-                }
+            if let Some(entry) = current_entry.take()
+                && !entry.sections.is_empty()
+            {
+                entries.push(entry);
             }
 
-            let version = version.trim();
-            let version = version
-                .strip_prefix('v')
-                .or_else(|| version.strip_prefix('V'))
-                .unwrap_or(version)
-                .to_string();
-            let anchor_id = release_anchor_id(&version);
-            current_entry = Some(ReleaseEntry {
-                version,
-                anchor_id,
-                sections: Vec::new(),
-            });
+            // Keep a Changelog files collect upcoming changes under an
+            // `[Unreleased]` heading, which is not a release.
+            current_entry = Some(parse_release_heading(heading))
+                .filter(|entry| !entry.version.eq_ignore_ascii_case("unreleased"));
             continue;
         }
 
@@ -346,12 +1902,11 @@ fn parse_release_notes_markdown(markdown: &str) -> Vec<ReleaseEntry> {
                 continue;
             }
 
-            if let Some(section) = current_section.take() {
-                if !section.items.is_empty() {
-                    if let Some(entry) = current_entry.as_mut() {
-                        entry.sections.push(section);
-                    }
-                }
+            if let Some(section) = current_section.take()
+                && !section.items.is_empty()
+                && let Some(entry) = current_entry.as_mut()
+            {
+                entry.sections.push(section);
             }
 
             current_section = Some(ReleaseSection {
@@ -361,6 +1916,15 @@ fn parse_release_notes_markdown(markdown: &str) -> Vec<ReleaseEntry> {
             continue;
         }
 
+        if let Some((label, url)) = parse_link_reference(line) {
+            // Tag links are rendered as `href`s, so they get the same check as
+            // links in release items.
+            if markdown::is_safe_url(url) {
+                tag_urls.push((strip_version_prefix(label).to_string(), url.to_string()));
+            }
+            continue;
+        }
+
         if let Some(item) = line.strip_prefix("- ") {
             if current_entry.is_none() {
                 continue;
@@ -374,28 +1938,127 @@ fn parse_release_notes_markdown(markdown: &str) -> Vec<ReleaseEntry> {
             }
 
             if let Some(section) = current_section.as_mut() {
-                section.items.push(item.trim().to_string());
+                let depth = list_indent_width(raw_line) / 2;
+                push_release_item(&mut section.items, depth, ReleaseItem::new(item.trim()));
             }
         }
     }
 
-    if let Some(section) = current_section {
-        if !section.items.is_empty() {
-            if let Some(entry) = current_entry.as_mut() {
-                entry.sections.push(section);
-            }
-        }
+    if let Some(section) = current_section
+        && !section.items.is_empty()
+        && let Some(entry) = current_entry.as_mut()
+    {
+        entry.sections.push(section);
     }
 
-    if let Some(entry) = current_entry {
-        if !entry.sections.is_empty() {
-            entries.push(entry);
-        }
+    if let Some(entry) = current_entry
+        && !entry.sections.is_empty()
+    {
+        entries.push(entry);
+    }
+
+    // Release notes are normally written newest-first, but order by semver
+    // precedence so an out-of-place heading can't break navigation or compare.
+    entries.sort_by(|left, right| {
+        SemanticVersion::parse(&right.version).cmp(&SemanticVersion::parse(&left.version))
+    });
+
+    for entry in &mut entries {
+        entry.tag_url = tag_urls
+            .iter()
+            .find(|(version, _)| *version == entry.version)
+            .map(|(_, url)| url.clone());
     }
 
     entries
 }
 
+fn list_indent_width(raw_line: &str) -> usize {
+    raw_line
+        .chars()
+        .take_while(|character| character.is_whitespace())
+        .map(|character| if character == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+fn push_release_item(items: &mut Vec<ReleaseItem>, depth: usize, item: ReleaseItem) {
+    // Nested bullets attach to the most recent item one level up; bullets
+    // indented deeper than the current tree simply nest as far as possible.
+    match items.last_mut() {
+        Some(parent) if depth > 0 => push_release_item(&mut parent.children, depth - 1, item),
+        _ => items.push(item),
+    }
+}
+
+fn parse_release_heading(heading: &str) -> ReleaseEntry {
+    // Accepts both plain `## 0.9.0` headings and Keep a Changelog style
+    // `## [0.9.0] - 2026-03-14 [YANKED]` headings.
+    let heading = heading.trim();
+    let (version, details) = match heading
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once(']'))
+    {
+        Some((version, details)) => (version, details),
+        None => heading.split_once(" - ").unwrap_or((heading, "")),
+    };
+    let version = strip_version_prefix(version.trim()).to_string();
+    let details = details.to_ascii_uppercase();
+
+    let date = details
+        .split(|character: char| character.is_whitespace() || character == '[' || character == ']')
+        .find_map(parse_iso_date);
+    let released_at =
+        date.map(|(year, month, day)| unix_days_from_civil_date(year, month, day) as u64 * 86_400);
+    let date = date.map(|(year, month, day)| format!("{year:04}-{month:02}-{day:02}"));
+    let yanked = details.contains("[YANKED]");
+    let prerelease = details.contains("[PRE-RELEASE]")
+        || details.contains("[PRERELEASE]")
+        || version.contains('-');
+    let anchor_id = release_anchor_id(&version);
+
+    ReleaseEntry {
+        version,
+        anchor_id,
+        date,
+        released_at,
+        tag_url: None,
+        yanked,
+        prerelease,
+        sections: Vec::new(),
+    }
+}
+
+fn parse_link_reference(line: &str) -> Option<(&str, &str)> {
+    let (label, url) = line.strip_prefix('[')?.split_once("]:")?;
+    let url = url.trim();
+    if label.is_empty() || url.is_empty() {
+        return None;
+    }
+
+    Some((label.trim(), url))
+}
+
+fn strip_version_prefix(version: &str) -> &str {
+    version
+        .strip_prefix('v')
+        .or_else(|| version.strip_prefix('V'))
+        .unwrap_or(version)
+}
+
+fn parse_iso_date(value: &str) -> Option<(i32, u32, u32)> {
+    let mut parts = value.split('-');
+    let year = parts.next().filter(|part| part.len() == 4)?.parse().ok()?;
+    let month = parts.next().filter(|part| part.len() == 2)?.parse().ok()?;
+    let day = parts.next().filter(|part| part.len() == 2)?.parse().ok()?;
+    if parts.next().is_some() || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    let days = unix_days_from_civil_date(year, month, day);
+    (days >= 0 && civil_date_from_unix_days(days) == (year, month, day))
+        .then_some((year, month, day))
+}
+
 fn release_anchor_id(version: &str) -> String {
     let mut anchor_id = String::from("v");
 
@@ -425,7 +2088,11 @@ fn current_year() -> i32 {
 }
 
 fn year_from_unix_days(days_since_epoch: i64) -> i32 {
-    // Convert Unix days to Gregorian year using a civil date algorithm.
+    civil_date_from_unix_days(days_since_epoch).0
+}
+
+fn civil_date_from_unix_days(days_since_epoch: i64) -> (i32, u32, u32) {
+    // Convert Unix days to a Gregorian date using a civil date algorithm.
     let z = days_since_epoch + 719_468;
     let era = if z >= 0 { z } else { z - 146_096 }.div_euclid(146_097);
     let doe = z - era * 146_097;
@@ -433,131 +2100,399 @@ fn year_from_unix_days(days_since_epoch: i64) -> i32 {
     let mut year = yoe + era * 400;
     let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
     let mp = (5 * doy + 2).div_euclid(153);
+    let day = doy - (153 * mp + 2).div_euclid(5) + 1;
     let month = mp + if mp < 10 { 3 } else { -9 };
 
     year += if month <= 2 { 1 } else { 0 };
-    year as i32
+    (year as i32, month as u32, day as u32)
 }
 
-fn load_transmissions() -> TransmissionState {
-    match fs::read_to_string(TRANSMISSIONS_PATH) {
-        Ok(content) => match serde_json::from_str::<TransmissionState>(&content) {
-            Ok(state) if !state.entries.is_empty() => state,
-            Ok(_) | Err(_) => {
-                let state = default_transmissions();
-                let _ = persist_transmissions(&state);
-                state
-            }
-        },
-        Err(_) => {
-            let state = default_transmissions();
-            let _ = persist_transmissions(&state);
-            state
+fn unix_days_from_civil_date(year: i32, month: u32, day: u32) -> i64 {
+    // Inverse of `civil_date_from_unix_days`.
+    let year = i64::from(year) - if month <= 2 { 1 } else { 0 };
+    let era = if year >= 0 { year } else { year - 399 }.div_euclid(400);
+    let yoe = year - era * 400;
+    let month = i64::from(month);
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2).div_euclid(5) + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn rfc3339_from_unix(unix_seconds: u64) -> String {
+    let (year, month, day) = civil_date_from_unix_days((unix_seconds / 86_400) as i64);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{}Z",
+        clock_label_from_unix(unix_seconds)
+    )
+}
+
+fn rfc2822_from_unix(unix_seconds: u64) -> String {
+    const WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+    let days_since_epoch = unix_seconds / 86_400;
+    let (year, month, day) = civil_date_from_unix_days(days_since_epoch as i64);
+    format!(
+        "{}, {day:02} {} {year:04} {} +0000",
+        WEEKDAYS[(days_since_epoch % 7) as usize],
+        MONTHS[(month - 1) as usize],
+        clock_label_from_unix(unix_seconds)
+    )
+}
+
+/// Formats an IMF-fixdate, the form HTTP headers such as `Last-Modified` use.
+fn http_date_from_unix(unix_seconds: u64) -> String {
+    rfc2822_from_unix(unix_seconds).replace("+0000", "GMT")
+}
+
+/// Parses an IMF-fixdate such as `Sun, 06 Nov 1994 08:49:37 GMT`.
+fn unix_from_http_date(value: &str) -> Option<u64> {
+    let [_, day, month, year, clock, "GMT"] = value.split_whitespace().collect::<Vec<_>>()[..]
+    else {
+        return None;
+    };
+    let day: u32 = day.parse().ok()?;
+    let month = MONTHS.iter().position(|candidate| *candidate == month)? as u32 + 1;
+    let year: i32 = year.parse().ok()?;
+    let mut clock = clock.split(':').map(|part| part.parse::<u64>().ok());
+    let (hours, minutes, seconds) = (clock.next()??, clock.next()??, clock.next()??);
+    if !(1..=31).contains(&day) || hours > 23 || minutes > 59 || seconds > 60 {
+        return None;
+    }
+
+    let days = u64::try_from(unix_days_from_civil_date(year, month, day)).ok()?;
+    Some(days * 86_400 + hours * 3_600 + minutes * 60 + seconds)
+}
+
+fn load_or_default_transmissions(
+    store: &dyn TransmissionStore,
+    configured_seed: Option<u64>,
+) -> TransmissionState {
+    let (mut state, mut changed) = match store.load(MAX_TRANSMISSIONS) {
+        Ok(Some(state)) => (state, false),
+        Ok(None) => (default_transmissions(), true),
+        Err(error) => {
+            error!(%error, "failed to load transmissions; starting from default transmissions");
+            (default_transmissions(), true)
         }
+    };
+    changed |= seed_transmission_generator(&mut state, configured_seed);
+    if state.modified_at == 0 {
+        let newest = state.entries.first().map_or(0, |entry| entry.timestamp);
+        state.modified_at = newest.max(state.last_generated_at);
+    }
+
+    if changed && let Err(error) = store.save(&state) {
+        METRICS.persistence_failed();
+        error!(%error, "failed to persist transmissions");
     }
+    METRICS.set_last_generated_at(state.last_generated_at);
+    state
+}
+
+/// Restarts the generator from `configured_seed` when it differs from the
+/// stored one, or from a clock-derived seed when no seed was ever chosen.
+/// Returns whether `state` changed.
+fn seed_transmission_generator(
+    state: &mut TransmissionState,
+    configured_seed: Option<u64>,
+) -> bool {
+    let seed = match (configured_seed, state.seed) {
+        (Some(configured), stored) if stored != Some(configured) => configured,
+        (None, None) => SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_nanos() as u64)
+            .unwrap_or_default(),
+        _ => return false,
+    };
+
+    info!(seed, "seeding the transmission generator");
+    state.seed = Some(seed);
+    state.rng_state = seed;
+    true
 }
 
 fn default_transmissions() -> TransmissionState {
     let now = unix_now_secs();
     TransmissionState {
         last_generated_at: now,
+        seed: None,
+        rng_state: 0,
+        last_seen_version: None,
+        modified_at: now,
         entries: vec![
             TransmissionEntry {
                 timestamp: now.saturating_sub(1_200),
-                time_label: clock_label_from_unix(now.saturating_sub(1_200)),
                 message: "Uplink stabilized. Archive index pushed to public relay.".to_string(),
+                kind: TransmissionKind::Generated,
+                link: None,
             },
             TransmissionEntry {
                 timestamp: now.saturating_sub(3_300),
-                time_label: clock_label_from_unix(now.saturating_sub(3_300)),
                 message: "Detected repeating pattern in ambient static. Logged as anomaly A-17."
                     .to_string(),
+                kind: TransmissionKind::Generated,
+                link: None,
             },
             TransmissionEntry {
                 timestamp: now.saturating_sub(5_800),
-                time_label: clock_label_from_unix(now.saturating_sub(5_800)),
                 message: "Scheduled new broadcast: Deep Space Transmitter.".to_string(),
+                kind: TransmissionKind::Generated,
+                link: None,
             },
         ],
     }
 }
 
-fn persist_transmissions(state: &TransmissionState) -> std::io::Result<()> {
-    if let Some(parent) = Path::new(TRANSMISSIONS_PATH).parent() {
-        fs::create_dir_all(parent)?;
+struct TransmissionGrammarCache {
+    modified_at: Option<SystemTime>,
+    grammar: Arc<Grammar>,
+}
+
+/// Returns the generator grammar, re-reading the file when it has changed and
+/// keeping the previous grammar if the new one fails to load.
+async fn load_transmission_grammar(app_state: &AppState) -> Arc<Grammar> {
+    let path = &app_state.config.transmission_grammar_path;
+    let modified_at = fs::metadata(path).and_then(|metadata| metadata.modified());
+    let mut cache = app_state.transmission_grammar.write().await;
+
+    match modified_at {
+        Ok(modified_at) if cache.modified_at != Some(modified_at) => {
+            match fs::read_to_string(path)
+                .map_err(|error| error.to_string())
+                .and_then(|source| Grammar::parse(&source))
+            {
+                Ok(grammar) => cache.grammar = Arc::new(grammar),
+                Err(error) => error!(
+                    path = %path.display(),
+                    %error,
+                    "failed to load transmission grammar; keeping the previous grammar"
+                ),
+            }
+            cache.modified_at = Some(modified_at);
+        }
+        Ok(_) => {}
+        Err(error) => {
+            if cache.modified_at.take().is_some() {
+                error!(
+                    path = %path.display(),
+                    %error,
+                    "failed to read transmission grammar; keeping the previous grammar"
+                );
+            }
+        }
     }
 
-    let json = serde_json::to_string_pretty(state).map_err(std::io::Error::other)?;
-    fs::write(TRANSMISSIONS_PATH, json)
+    cache.grammar.clone()
 }
 
+#[instrument(name = "generate_transmissions", skip_all)]
 async fn generate_if_needed_and_persist(app_state: &AppState) {
     let now = unix_now_secs();
-    let snapshot = {
+    let grammar = load_transmission_grammar(app_state).await;
+    let latest_release = load_release_notes(app_state).await.first().cloned();
+    let (last_generated_at, mut added) = {
         let mut guard = app_state.transmissions.write().await;
-        if maybe_generate_transmission(&mut guard, now) {
-            Some(guard.clone())
-        } else {
-            None
+        let announced = maybe_announce_release(&mut guard, &grammar, latest_release.as_ref(), now);
+        let generated = maybe_generate_transmission(
+            &mut guard,
+            &grammar,
+            now,
+            app_state.config.generation_interval_secs,
+            app_state.config.backfill_missed_transmissions,
+        );
+        if announced.is_none() && generated.is_none() {
+            return;
+        }
+        // Saved under the lock, so an admin delete that follows cannot be
+        // undone by this save landing after it.
+        if let Err(error) = app_state.store.save(&guard) {
+            METRICS.persistence_failed();
+            error!(%error, "failed to persist transmissions");
         }
+        let added: Vec<_> = announced.into_iter().chain(generated).flatten().collect();
+        (guard.last_generated_at, added)
     };
+    METRICS.set_last_generated_at(last_generated_at);
+    added.sort_by_key(|entry| entry.timestamp);
+    for entry in added {
+        info!(
+            transmission = entry.timestamp,
+            kind = entry.kind.as_str(),
+            "logged transmission"
+        );
+        METRICS.transmission_logged(entry.kind.as_str());
+        // Sending only fails when no stream is connected, which is fine.
+        let _ = app_state.transmission_events.send(entry);
+    }
+}
 
-    if let Some(state) = snapshot
-        && let Err(error) = persist_transmissions(&state)
-    {
-        eprintln!("failed to persist transmissions: {error}");
+/// Announces `latest_release` when it is newer than the last version seen.
+/// The first version ever seen is only recorded, so existing logs are not
+/// flooded with announcements for old releases. Returns the transmissions
+/// added, or `None` when `state` is unchanged.
+fn maybe_announce_release(
+    state: &mut TransmissionState,
+    grammar: &Grammar,
+    latest_release: Option<&ReleaseEntry>,
+    now: u64,
+) -> Option<Vec<TransmissionEntry>> {
+    let release = latest_release?;
+    let Some(last_seen_version) = &state.last_seen_version else {
+        state.last_seen_version = Some(release.version.clone());
+        return Some(Vec::new());
+    };
+    if *last_seen_version == release.version {
+        return None;
     }
+
+    let is_newer = match (
+        SemanticVersion::parse(&release.version),
+        SemanticVersion::parse(last_seen_version),
+    ) {
+        (Some(latest), Some(last_seen)) => latest > last_seen,
+        _ => true,
+    };
+    state.last_seen_version = Some(release.version.clone());
+    if !is_newer {
+        return Some(Vec::new());
+    }
+
+    let message = grammar
+        .release_announcement(&release.version, &mut || splitmix64(&mut state.rng_state))
+        .unwrap_or_else(|| {
+            format!(
+                "Pirate beacon broadcasting Ethereal Waves {}.",
+                release.version
+            )
+        });
+    let announced = push_transmission(
+        state,
+        now,
+        message,
+        TransmissionKind::Release,
+        Some(format!("/ethereal-waves/changelog#{}", release.anchor_id)),
+    );
+    state.modified_at = now;
+    Some(announced.into_iter().collect())
 }
 
-fn maybe_generate_transmission(state: &mut TransmissionState, now: u64) -> bool {
-    if now.saturating_sub(state.last_generated_at) < GENERATION_INTERVAL_SECS {
-        return false;
+/// Generates a transmission once `interval_secs` have passed since the last
+/// one. With `backfill`, every interval missed while the server was down gets
+/// its own entry at the time it would have been generated, up to
+/// `MAX_TRANSMISSIONS` of the most recent ones. Returns the transmissions
+/// added, oldest first, or `None` when none was due.
+fn maybe_generate_transmission(
+    state: &mut TransmissionState,
+    grammar: &Grammar,
+    now: u64,
+    interval_secs: u64,
+    backfill: bool,
+) -> Option<Vec<TransmissionEntry>> {
+    let elapsed = now.saturating_sub(state.last_generated_at);
+    if elapsed < interval_secs {
+        return None;
     }
 
-    let message = generate_scifi_message(now, state.entries.len());
-    state.entries.insert(
-        0,
-        TransmissionEntry {
-            timestamp: now,
-            time_label: clock_label_from_unix(now),
+    if !backfill {
+        let message = generate_unique_message(state, grammar);
+        let generated = push_transmission(state, now, message, TransmissionKind::Generated, None);
+        state.last_generated_at = now;
+        state.modified_at = now;
+        return Some(generated.into_iter().collect());
+    }
+
+    let missed = elapsed / interval_secs;
+    let first = missed - missed.min(MAX_TRANSMISSIONS as u64) + 1;
+    let mut generated = Vec::new();
+    for window in first..=missed {
+        let timestamp = state.last_generated_at + window * interval_secs;
+        let message = generate_unique_message(state, grammar);
+        generated.extend(push_transmission(
+            state,
+            timestamp,
             message,
-        },
-    );
+            TransmissionKind::Generated,
+            None,
+        ));
+    }
+    // Newer entries already in the log can push early backfills out again.
+    generated.retain(|entry| {
+        state
+            .entries
+            .iter()
+            .any(|kept| kept.timestamp == entry.timestamp)
+    });
+    // Staying on the original schedule keeps later windows evenly spaced.
+    state.last_generated_at += missed * interval_secs;
+    state.modified_at = now;
+    Some(generated)
+}
+
+/// Adds a transmission at `timestamp`, or the next free second after it since
+/// timestamps identify transmissions, keeping entries newest first. Returns
+/// the entry, or `None` when it is older than every entry kept, which only
+/// happens after the clock steps backwards.
+fn push_transmission(
+    state: &mut TransmissionState,
+    timestamp: u64,
+    message: String,
+    kind: TransmissionKind,
+    link: Option<String>,
+) -> Option<TransmissionEntry> {
+    let mut timestamp = timestamp;
+    while state
+        .entries
+        .iter()
+        .any(|entry| entry.timestamp == timestamp)
+    {
+        timestamp += 1;
+    }
+
+    let index = state
+        .entries
+        .partition_point(|entry| entry.timestamp > timestamp);
+    let entry = TransmissionEntry {
+        timestamp,
+        message,
+        kind,
+        link,
+    };
+    state.entries.insert(index, entry.clone());
     state.entries.truncate(MAX_TRANSMISSIONS);
-    state.last_generated_at = now;
-    true
+    (index < MAX_TRANSMISSIONS).then_some(entry)
+}
+
+/// Draws messages from the state's generator until one differs from the
+/// recent entries, settling for the last draw if the grammar is too small.
+fn generate_unique_message(state: &mut TransmissionState, grammar: &Grammar) -> String {
+    let mut message = String::new();
+
+    for _ in 0..MAX_GENERATION_ATTEMPTS {
+        message = grammar.generate(&mut || splitmix64(&mut state.rng_state));
+        if !state
+            .entries
+            .iter()
+            .take(RECENT_DUPLICATE_WINDOW)
+            .any(|entry| entry.message == message)
+        {
+            return message;
+        }
+    }
+
+    warn!(
+        attempts = MAX_GENERATION_ATTEMPTS,
+        "transmission grammar produced only recent messages; repeating one"
+    );
+    message
 }
 
-fn generate_scifi_message(now: u64, entry_count: usize) -> String {
-    let subjects = [
-        "Long-range scanner",
-        "Relay drone",
-        "Pirate beacon",
-        "Outer rim array",
-        "Subspace receiver",
-        "Navigation core",
-    ];
-    let actions = [
-        "locked onto",
-        "decoded",
-        "flagged",
-        "stabilized",
-        "rerouted",
-        "intercepted",
-    ];
-    let objects = [
-        "a drifting colony ping",
-        "an encrypted trader channel",
-        "a rogue moon telemetry burst",
-        "a hidden wormhole marker",
-        "an ion storm distress packet",
-        "a ghost-fleet handshake",
-    ];
-
-    let s = ((now / 7) as usize + entry_count * 3) % subjects.len();
-    let a = ((now / 11) as usize + entry_count * 5) % actions.len();
-    let o = ((now / 13) as usize + entry_count * 7) % objects.len();
-    format!("{} {} {}.", subjects[s], actions[a], objects[o])
+/// SplitMix64: a tiny, well-distributed generator for picking message parts.
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut value = *state;
+    value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    value ^ (value >> 31)
 }
 
 fn clock_label_from_unix(unix_seconds: u64) -> String {
@@ -575,12 +2510,219 @@ fn unix_now_secs() -> u64 {
         .as_secs()
 }
 
-fn start_transmission_generator(app_state: AppState) {
+/// The only place transmissions are generated. The generator sleeps until the
+/// next transmission is due, or `GENERATOR_TICK_SECS` at most. Each wait is
+/// measured against the wall clock, so ticks missed while the host was
+/// suspended or busy are caught up in one pass instead of a burst.
+fn start_transmission_generator(
+    app_state: AppState,
+    mut shutdown: watch::Receiver<bool>,
+) -> JoinHandle<()> {
     tokio::spawn(async move {
-        let mut ticker = tokio::time::interval(Duration::from_secs(300));
         loop {
-            ticker.tick().await;
-            generate_if_needed_and_persist(&app_state).await;
+            let delay = next_generator_delay(&app_state).await;
+            tokio::select! {
+                () = tokio::time::sleep(delay) => run_generator_tick(&app_state).await,
+                _ = shutdown.changed() => break,
+            }
         }
-    });
+    })
+}
+
+async fn run_generator_tick(app_state: &AppState) {
+    METRICS.generator_heartbeat(unix_now_secs());
+    generate_if_needed_and_persist(app_state).await;
+}
+
+async fn next_generator_delay(app_state: &AppState) -> Duration {
+    let last_generated_at = app_state.transmissions.read().await.last_generated_at;
+    let due_at = last_generated_at.saturating_add(app_state.config.generation_interval_secs);
+    let until_due = due_at.saturating_sub(unix_now_secs());
+    Duration::from_secs(until_due.clamp(1, GENERATOR_TICK_SECS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START: u64 = 1_700_000_000;
+    const INTERVAL: u64 = 3_600;
+
+    fn seeded_state(seed: u64) -> TransmissionState {
+        let mut state = TransmissionState {
+            last_generated_at: START,
+            seed: None,
+            rng_state: 0,
+            last_seen_version: None,
+            modified_at: START,
+            entries: Vec::new(),
+        };
+        seed_transmission_generator(&mut state, Some(seed));
+        state
+    }
+
+    fn entry(timestamp: u64, message: &str) -> TransmissionEntry {
+        TransmissionEntry {
+            timestamp,
+            message: message.to_string(),
+            kind: TransmissionKind::Generated,
+            link: None,
+        }
+    }
+
+    fn log(state: &TransmissionState) -> Vec<(u64, String)> {
+        state
+            .entries
+            .iter()
+            .map(|entry| (entry.timestamp, entry.message.clone()))
+            .collect()
+    }
+
+    fn run_generator(seed: u64, backfill: bool) -> Vec<(u64, String)> {
+        let grammar = Grammar::bundled();
+        let mut state = seeded_state(seed);
+        for tick in 1..=20 {
+            maybe_generate_transmission(
+                &mut state,
+                &grammar,
+                START + tick * INTERVAL * 2,
+                INTERVAL,
+                backfill,
+            );
+        }
+        log(&state)
+    }
+
+    #[test]
+    fn same_seed_reproduces_the_log() {
+        let first = run_generator(42, false);
+        assert_eq!(first.len(), MAX_TRANSMISSIONS);
+        assert_eq!(first, run_generator(42, false));
+        assert_ne!(first, run_generator(43, false));
+    }
+
+    #[test]
+    fn same_seed_reproduces_a_backfilled_log() {
+        let first = run_generator(7, true);
+        assert_eq!(first.len(), MAX_TRANSMISSIONS);
+        assert_eq!(first, run_generator(7, true));
+    }
+
+    #[test]
+    fn backfill_reports_windows_older_than_a_newer_post() {
+        let grammar = Grammar::bundled();
+        let mut state = seeded_state(3);
+        state.entries = vec![entry(START + 8 * INTERVAL + 30, "manual")];
+
+        let added = maybe_generate_transmission(
+            &mut state,
+            &grammar,
+            START + 10 * INTERVAL,
+            INTERVAL,
+            true,
+        );
+        let timestamps: Vec<_> = added
+            .unwrap_or_default()
+            .iter()
+            .map(|entry| entry.timestamp)
+            .collect();
+        assert_eq!(
+            timestamps,
+            (1..=10)
+                .map(|window| START + window * INTERVAL)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn recent_messages_are_not_repeated() {
+        let grammar = Grammar::parse(r#"message = ["alpha", "beta"]"#).unwrap();
+        let mut state = seeded_state(1);
+
+        for _ in 0..50 {
+            state.entries = vec![entry(START, "alpha")];
+            assert_eq!(generate_unique_message(&mut state, &grammar), "beta");
+        }
+    }
+
+    #[test]
+    fn messages_older_than_the_window_may_repeat() {
+        let grammar = Grammar::parse(r#"message = ["alpha", "beta"]"#).unwrap();
+        let mut state = seeded_state(1);
+
+        for _ in 0..50 {
+            state.entries = (0..RECENT_DUPLICATE_WINDOW as u64)
+                .map(|age| entry(START - age, "beta"))
+                .chain([entry(START - 1_000, "alpha")])
+                .collect();
+            assert_eq!(generate_unique_message(&mut state, &grammar), "alpha");
+        }
+    }
+
+    #[test]
+    fn http_dates_round_trip() {
+        assert_eq!(
+            http_date_from_unix(784_111_777),
+            "Sun, 06 Nov 1994 08:49:37 GMT"
+        );
+        for timestamp in [0, 784_111_777, START, START + 29 * 86_400 + 3_661] {
+            assert_eq!(
+                unix_from_http_date(&http_date_from_unix(timestamp)),
+                Some(timestamp)
+            );
+        }
+        assert_eq!(unix_from_http_date("Sun, 06 Foo 1994 08:49:37 GMT"), None);
+    }
+
+    #[test]
+    fn unsafe_tag_links_are_dropped() {
+        let entries = parse_release_notes_markdown(
+            "## 1.1.0\n\n- Safe.\n\n## 1.0.0\n\n- Unsafe.\n\n\
+             [1.1.0]: https://example.com/tags/1.1.0\n\
+             [1.0.0]: javascript:alert(1)\n",
+        );
+        let tag_urls: Vec<_> = entries
+            .iter()
+            .map(|entry| (entry.version.as_str(), entry.tag_url.as_deref()))
+            .collect();
+        assert_eq!(
+            tag_urls,
+            [
+                ("1.1.0", Some("https://example.com/tags/1.1.0")),
+                ("1.0.0", None)
+            ]
+        );
+    }
+
+    #[test]
+    fn unreleased_changes_are_not_a_release() {
+        let entries = parse_release_notes_markdown(
+            "## [Unreleased]\n\n### Added\n- Upcoming.\n\n\
+             ## [1.0.0] - 2026-01-02\n\n### Added\n- Released.\n\n\
+             [Unreleased]: https://example.com/compare/v1.0.0...HEAD\n",
+        );
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].version, "1.0.0");
+        assert_eq!(entries[0].sections[0].items.len(), 1);
+    }
+
+    #[test]
+    fn manual_links_must_stay_on_the_site_or_the_web() {
+        for link in [
+            "/transmissions",
+            "https://example.com",
+            "http://example.com",
+        ] {
+            assert!(is_manual_transmission_link(link), "{link}");
+        }
+        for link in [
+            "//evil.example",
+            "/\\evil.example",
+            "javascript:alert(1)",
+            "mailto:a@b.c",
+            "#top",
+        ] {
+            assert!(!is_manual_transmission_link(link), "{link}");
+        }
+    }
 }
@@ -0,0 +1,286 @@
+const ISSUES_URL: &str = "https://github.com/cosmic-utils/ethereal-waves/issues";
+
+/// Renders a single line of inline Markdown (code spans, emphasis, links and
+/// `#123` issue references) into escaped HTML.
+pub(crate) fn render_inline(text: &str) -> String {
+    render_spans(text, true)
+}
+
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for character in value.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(character),
+        }
+    }
+
+    escaped
+}
+
+fn render_spans(text: &str, allow_links: bool) -> String {
+    let mut html = String::with_capacity(text.len());
+    let mut rest = text;
+    let mut previous: Option<char> = None;
+
+    while let Some(character) = rest.chars().next() {
+        if let Some((rendered, consumed)) = render_span(rest, previous, allow_links) {
+            html.push_str(&rendered);
+            previous = rest[..consumed].chars().next_back();
+            rest = &rest[consumed..];
+            continue;
+        }
+
+        html.push_str(&escape_html(&rest[..character.len_utf8()]));
+        previous = Some(character);
+        rest = &rest[character.len_utf8()..];
+    }
+
+    html
+}
+
+fn render_span(rest: &str, previous: Option<char>, allow_links: bool) -> Option<(String, usize)> {
+    let at_word_start = previous.is_none_or(|character| !character.is_alphanumeric());
+
+    if let Some(code) = rest.strip_prefix('`') {
+        let end = code.find('`')?;
+        if end == 0 {
+            return None;
+        }
+        return Some((
+            format!("<code>{}</code>", escape_html(&code[..end])),
+            end + 2,
+        ));
+    }
+
+    for marker in ["**", "__"] {
+        if let Some(inner) = rest.strip_prefix(marker) {
+            if marker == "__" && !at_word_start {
+                return None;
+            }
+            let end = inner.find(marker)?;
+            if end == 0 || inner.starts_with(char::is_whitespace) {
+                return None;
+            }
+            return Some((
+                format!(
+                    "<strong>{}</strong>",
+                    render_spans(&inner[..end], allow_links)
+                ),
+                end + 4,
+            ));
+        }
+    }
+
+    for marker in ['*', '_'] {
+        if let Some(inner) = rest.strip_prefix(marker) {
+            if marker == '_' && !at_word_start {
+                return None;
+            }
+            let end = inner.find(marker)?;
+            let after = inner[end + 1..].chars().next();
+            if end == 0
+                || inner.starts_with(char::is_whitespace)
+                || (marker == '_' && after.is_some_and(char::is_alphanumeric))
+            {
+                return None;
+            }
+            return Some((
+                format!("<em>{}</em>", render_spans(&inner[..end], allow_links)),
+                end + 2,
+            ));
+        }
+    }
+
+    if allow_links && let Some(inner) = rest.strip_prefix('[') {
+        let (label, after_label) = inner.split_once("](")?;
+        let url = &after_label[..closing_paren_index(after_label)?];
+        let consumed = 1 + label.len() + 2 + url.len() + 1;
+        let label = render_spans(label, false);
+        let url = url.trim();
+        if !is_safe_url(url) {
+            return Some((label, consumed));
+        }
+        return Some((
+            format!("<a href=\"{}\">{label}</a>", escape_html(url)),
+            consumed,
+        ));
+    }
+
+    if allow_links
+        && at_word_start
+        && let Some(digits) = rest.strip_prefix('#')
+    {
+        let end = digits
+            .find(|character: char| !character.is_ascii_digit())
+            .unwrap_or(digits.len());
+        let after = digits[end..].chars().next();
+        if end == 0 || after.is_some_and(char::is_alphanumeric) {
+            return None;
+        }
+        let number = &digits[..end];
+        return Some((
+            format!("<a href=\"{ISSUES_URL}/{number}\">#{number}</a>"),
+            end + 1,
+        ));
+    }
+
+    None
+}
+
+fn closing_paren_index(text: &str) -> Option<usize> {
+    let mut depth = 0usize;
+
+    for (index, character) in text.char_indices() {
+        match character {
+            '(' => depth += 1,
+            ')' if depth == 0 => return Some(index),
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Whether `url` may be placed in an `href`: web and mail links, and paths
+/// or fragments on this site. Protocol-relative `//` URLs are refused,
+/// including the spellings browsers read the same way: `\` for `/`, with
+/// tabs and newlines ignored.
+pub(crate) fn is_safe_url(url: &str) -> bool {
+    let normalized: String = url
+        .chars()
+        .filter(|character| !matches!(character, '\t' | '\n' | '\r'))
+        .map(|character| match character {
+            '\\' => '/',
+            character => character.to_ascii_lowercase(),
+        })
+        .collect();
+    ["https://", "http://", "mailto:", "/", "#"]
+        .iter()
+        .any(|prefix| normalized.starts_with(prefix))
+        && !normalized.starts_with("//")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue_link(number: u32) -> String {
+        format!("<a href=\"{ISSUES_URL}/{number}\">#{number}</a>")
+    }
+
+    #[test]
+    fn plain_text_is_escaped() {
+        assert_eq!(
+            render_inline("<script>alert('x') & \"y\"</script>"),
+            "&lt;script&gt;alert(&#39;x&#39;) &amp; &quot;y&quot;&lt;/script&gt;"
+        );
+        assert_eq!(
+            render_inline("`<b>` stays code"),
+            "<code>&lt;b&gt;</code> stays code"
+        );
+    }
+
+    #[test]
+    fn unsafe_links_render_only_their_label() {
+        for input in [
+            "[click](javascript:alert(1))",
+            "[click](JavaScript:alert(1))",
+            "[click](data:text/html,hi)",
+            "[click](//evil.example/path)",
+            "[click](/\\evil.example/path)",
+        ] {
+            assert_eq!(render_inline(input), "click", "{input}");
+        }
+    }
+
+    #[test]
+    fn safe_links_are_rendered_with_an_escaped_href() {
+        assert_eq!(
+            render_inline("[x](/docs) and [y](#top) and [m](mailto:a@b.c)"),
+            "<a href=\"/docs\">x</a> and <a href=\"#top\">y</a> and <a href=\"mailto:a@b.c\">m</a>"
+        );
+        assert_eq!(
+            render_inline("[x](https://example.com/\"onmouseover=\"x)"),
+            "<a href=\"https://example.com/&quot;onmouseover=&quot;x\">x</a>"
+        );
+        assert_eq!(
+            render_inline("[**bold** label](/a)"),
+            "<a href=\"/a\"><strong>bold</strong> label</a>"
+        );
+    }
+
+    #[test]
+    fn url_safety_is_decided_by_scheme() {
+        for url in [
+            "https://a.b",
+            "HTTP://a.b",
+            "mailto:a@b.c",
+            "/path",
+            "#anchor",
+        ] {
+            assert!(is_safe_url(url), "{url}");
+        }
+        for url in [
+            "javascript:alert(1)",
+            "//a.b",
+            "/\\a.b",
+            "/\t/a.b",
+            "data:text/html,hi",
+            "a.b",
+            "",
+        ] {
+            assert!(!is_safe_url(url), "{url}");
+        }
+    }
+
+    #[test]
+    fn emphasis_nests() {
+        assert_eq!(
+            render_inline("**bold _and italic_**"),
+            "<strong>bold <em>and italic</em></strong>"
+        );
+        assert_eq!(
+            render_inline("_under **strong**_"),
+            "<em>under <strong>strong</strong></em>"
+        );
+        assert_eq!(
+            render_inline("*italic and `code`*"),
+            "<em>italic and <code>code</code></em>"
+        );
+    }
+
+    #[test]
+    fn issue_references_need_word_boundaries() {
+        assert_eq!(
+            render_inline("fixes #123, see (#45)."),
+            format!("fixes {}, see ({}).", issue_link(123), issue_link(45))
+        );
+        assert_eq!(
+            render_inline("abc#123 and #12a and #"),
+            "abc#123 and #12a and #"
+        );
+    }
+
+    #[test]
+    fn unterminated_markers_are_literal() {
+        for input in [
+            "**unterminated",
+            "*unterminated",
+            "`unterminated",
+            "[unterminated](/a",
+            "snake_case_name stays",
+            "** not bold**",
+            "a * b * c",
+        ] {
+            assert_eq!(render_inline(input), input);
+        }
+        assert_eq!(render_inline("*<b>"), "*&lt;b&gt;");
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Upper bounds, in seconds, of the request latency histogram buckets.
+const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Process-wide counters and gauges, rendered by `/metrics` in the
+/// Prometheus text exposition format.
+pub(crate) static METRICS: Metrics = Metrics::new();
+
+pub(crate) struct Metrics {
+    requests: Mutex<BTreeMap<RequestKey, RequestStats>>,
+    transmissions_logged: Mutex<BTreeMap<&'static str, u64>>,
+    template_render_failures: AtomicU64,
+    persistence_failures: AtomicU64,
+    last_generated_at: AtomicU64,
+    generator_heartbeat: AtomicU64,
+    release_notes_entries: AtomicU64,
+    release_notes_parse_errors: AtomicU64,
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct RequestKey {
+    method: String,
+    route: String,
+    status: u16,
+}
+
+#[derive(Default)]
+struct RequestStats {
+    count: u64,
+    sum_seconds: f64,
+    buckets: [u64; LATENCY_BUCKETS.len()],
+}
+
+impl Metrics {
+    const fn new() -> Self {
+        Self {
+            requests: Mutex::new(BTreeMap::new()),
+            transmissions_logged: Mutex::new(BTreeMap::new()),
+            template_render_failures: AtomicU64::new(0),
+            persistence_failures: AtomicU64::new(0),
+            last_generated_at: AtomicU64::new(0),
+            generator_heartbeat: AtomicU64::new(0),
+            release_notes_entries: AtomicU64::new(0),
+            release_notes_parse_errors: AtomicU64::new(0),
+        }
+    }
+
+    /// `route` should be the matched route pattern rather than the raw path,
+    /// so the number of series stays bounded.
+    pub(crate) fn record_request(&self, method: &str, route: &str, status: u16, latency: Duration) {
+        let key = RequestKey {
+            method: method.to_string(),
+            route: route.to_string(),
+            status,
+        };
+        let seconds = latency.as_secs_f64();
+        let mut requests = lock(&self.requests);
+        let stats = requests.entry(key).or_default();
+        stats.count += 1;
+        stats.sum_seconds += seconds;
+        for (bucket, bound) in stats.buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if seconds <= bound {
+                *bucket += 1;
+            }
+        }
+    }
+
+    pub(crate) fn transmission_logged(&self, kind: &'static str) {
+        *lock(&self.transmissions_logged).entry(kind).or_default() += 1;
+    }
+
+    pub(crate) fn template_render_failed(&self) {
+        self.template_render_failures
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn persistence_failed(&self) {
+        self.persistence_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn set_last_generated_at(&self, timestamp: u64) {
+        self.last_generated_at.store(timestamp, Ordering::Relaxed);
+    }
+
+    /// Records that the background generator woke up at `timestamp`.
+    pub(crate) fn generator_heartbeat(&self, timestamp: u64) {
+        self.generator_heartbeat.store(timestamp, Ordering::Relaxed);
+    }
+
+    pub(crate) fn last_generator_heartbeat(&self) -> u64 {
+        self.generator_heartbeat.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn release_notes_loaded(&self, entries: usize) {
+        self.release_notes_entries
+            .store(entries as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn release_notes_parse_failed(&self) {
+        self.release_notes_parse_errors
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn render(&self) -> String {
+        let mut output = String::new();
+
+        header(
+            &mut output,
+            "gpr_http_requests_total",
+            "counter",
+            "HTTP requests served, by method, route and status.",
+        );
+        let requests = lock(&self.requests);
+        for (key, stats) in requests.iter() {
+            let _ = writeln!(
+                output,
+                "gpr_http_requests_total{{{}}} {}",
+                key.labels(),
+                stats.count
+            );
+        }
+
+        header(
+            &mut output,
+            "gpr_http_request_duration_seconds",
+            "histogram",
+            "Time to produce an HTTP response, by method, route and status.",
+        );
+        for (key, stats) in requests.iter() {
+            let labels = key.labels();
+            for (bound, count) in LATENCY_BUCKETS.iter().zip(stats.buckets) {
+                let _ = writeln!(
+                    output,
+                    "gpr_http_request_duration_seconds_bucket{{{labels},le=\"{bound}\"}} {count}"
+                );
+            }
+            let _ = writeln!(
+                output,
+                "gpr_http_request_duration_seconds_bucket{{{labels},le=\"+Inf\"}} {}",
+                stats.count
+            );
+            let _ = writeln!(
+                output,
+                "gpr_http_request_duration_seconds_sum{{{labels}}} {}",
+                stats.sum_seconds
+            );
+            let _ = writeln!(
+                output,
+                "gpr_http_request_duration_seconds_count{{{labels}}} {}",
+                stats.count
+            );
+        }
+        drop(requests);
+
+        header(
+            &mut output,
+            "gpr_transmissions_logged_total",
+            "counter",
+            "Transmissions added to the station log, by kind.",
+        );
+        for (kind, count) in lock(&self.transmissions_logged).iter() {
+            let _ = writeln!(
+                output,
+                "gpr_transmissions_logged_total{{kind=\"{}\"}} {count}",
+                escape_label(kind)
+            );
+        }
+
+        for (name, kind, help, value) in [
+            (
+                "gpr_transmission_last_generated_timestamp_seconds",
+                "gauge",
+                "Unix time the generator last produced a transmission.",
+                &self.last_generated_at,
+            ),
+            (
+                "gpr_generator_heartbeat_timestamp_seconds",
+                "gauge",
+                "Unix time the background generator last woke up.",
+                &self.generator_heartbeat,
+            ),
+            (
+                "gpr_persistence_failures_total",
+                "counter",
+                "Failed attempts to write the transmission store.",
+                &self.persistence_failures,
+            ),
+            (
+                "gpr_template_render_failures_total",
+                "counter",
+                "HTML templates that failed to render.",
+                &self.template_render_failures,
+            ),
+            (
+                "gpr_release_notes_entries",
+                "gauge",
+                "Releases found in the most recent good parse of the release notes.",
+                &self.release_notes_entries,
+            ),
+            (
+                "gpr_release_notes_parse_errors_total",
+                "counter",
+                "Release notes loads that were unreadable or contained no releases.",
+                &self.release_notes_parse_errors,
+            ),
+        ] {
+            header(&mut output, name, kind, help);
+            let _ = writeln!(output, "{name} {}", value.load(Ordering::Relaxed));
+        }
+
+        output
+    }
+}
+
+impl RequestKey {
+    fn labels(&self) -> String {
+        format!(
+            "method=\"{}\",route=\"{}\",status=\"{}\"",
+            escape_label(&self.method),
+            escape_label(&self.route),
+            self.status
+        )
+    }
+}
+
+fn header(output: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(output, "# HELP {name} {help}");
+    let _ = writeln!(output, "# TYPE {name} {kind}");
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Metrics must keep working even if a thread panicked mid-update.
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -0,0 +1,869 @@
+use crate::config::{Config, StorageBackend};
+use crate::{TransmissionEntry, TransmissionKind, TransmissionState, unix_now_secs};
+use rusqlite::{Connection, OptionalExtension, params};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tracing::{error, info, warn};
+
+/// Durable storage for the station log. The in-memory `TransmissionState`
+/// only holds the most recent entries; a store may keep more history than
+/// that.
+pub(crate) trait TransmissionStore: Send + Sync {
+    /// Loads the generator state with at most `limit` of the newest entries,
+    /// or `None` when nothing has been stored yet.
+    fn load(&self, limit: usize) -> io::Result<Option<TransmissionState>>;
+
+    /// Records `state`. Entries that fell out of the in-memory window are not
+    /// removed from stores that keep full history.
+    fn save(&self, state: &TransmissionState) -> io::Result<()>;
+
+    /// Removes a transmission from the archive, returning whether it existed.
+    /// A removal also moves the stored state's `modified_at` to now.
+    fn delete(&self, timestamp: u64) -> io::Result<bool>;
+
+    /// Number of transmissions available in the archive.
+    fn count(&self) -> io::Result<usize>;
+
+    /// Archived transmissions, newest first.
+    fn list(&self, offset: usize, limit: usize) -> io::Result<Vec<TransmissionEntry>>;
+
+    /// Up to `limit` archived transmissions newer than `timestamp`, oldest
+    /// first, so callers can page forward without gaps.
+    fn after(&self, timestamp: u64, limit: usize) -> io::Result<Vec<TransmissionEntry>>;
+
+    fn get(&self, timestamp: u64) -> io::Result<Option<TransmissionEntry>>;
+
+    /// Timestamps of the archived transmissions immediately older and newer
+    /// than `timestamp`.
+    fn adjacent(&self, timestamp: u64) -> io::Result<(Option<u64>, Option<u64>)>;
+
+    /// Checks, without writing anything, that the store can still be saved
+    /// to: the last save succeeded and the storage is not read-only.
+    fn check_writable(&self) -> io::Result<()>;
+}
+
+pub(crate) fn open(config: &Config) -> io::Result<Arc<dyn TransmissionStore>> {
+    let json_store = JsonFileStore::new(config.transmissions_path());
+    match config.storage {
+        StorageBackend::Json => Ok(Arc::new(json_store)),
+        StorageBackend::Sqlite => Ok(Arc::new(SqliteStore::open(
+            &config.sqlite_path(),
+            &json_store,
+        )?)),
+    }
+}
+
+/// The original storage format: a single pretty-printed JSON file holding
+/// exactly the in-memory window.
+pub(crate) struct JsonFileStore {
+    path: PathBuf,
+    /// Held for a whole write, as every write goes through the same
+    /// temporary file.
+    writing: Mutex<()>,
+    last_save: LastSave,
+}
+
+impl JsonFileStore {
+    pub(crate) fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            writing: Mutex::new(()),
+            last_save: LastSave::default(),
+        }
+    }
+
+    /// Writes `state` to a temporary file, syncs it and renames it over the
+    /// store path, so readers only ever see a complete file. The previous
+    /// file is kept as a `.bak` copy for `load` to fall back on.
+    fn write_file(&self, state: &TransmissionState) -> io::Result<()> {
+        let _writing = self
+            .writing
+            .lock()
+            .map_err(|_| io::Error::other("transmission file lock poisoned"))?;
+        let path = self.path.as_path();
+        let parent = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty());
+        if let Some(parent) = parent {
+            fs::create_dir_all(parent)?;
+        }
+
+        let json = serde_json::to_string_pretty(state).map_err(io::Error::other)?;
+        let temp_path = sibling_path(path, ".tmp");
+        {
+            let mut file = fs::File::create(&temp_path)?;
+            file.write_all(json.as_bytes())?;
+            file.sync_all()?;
+        }
+
+        if read_json_file(path).is_ok() {
+            fs::copy(path, sibling_path(path, ".bak"))?;
+        }
+        fs::rename(&temp_path, path)?;
+
+        #[cfg(unix)]
+        fs::File::open(parent.unwrap_or(Path::new(".")))?.sync_all()?;
+
+        Ok(())
+    }
+
+    fn archived_entries(&self) -> io::Result<Vec<TransmissionEntry>> {
+        let mut entries = match read_json_file(&self.path) {
+            Ok(state) => state.entries,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(error) => return Err(error),
+        };
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.timestamp));
+        Ok(entries)
+    }
+}
+
+impl TransmissionStore for JsonFileStore {
+    fn load(&self, limit: usize) -> io::Result<Option<TransmissionState>> {
+        let path = self.path.as_path();
+        let error = match read_json_file(path) {
+            Ok(mut state) => {
+                state.entries.truncate(limit);
+                return Ok(Some(state));
+            }
+            Err(error) => error,
+        };
+
+        let backup_path = sibling_path(path, ".bak");
+        if error.kind() == io::ErrorKind::NotFound && !backup_path.exists() {
+            return Ok(None);
+        }
+
+        error!(path = %path.display(), %error, "failed to load transmissions");
+        if path.exists() {
+            let corrupt_path = sibling_path(path, &format!(".corrupt-{}", unix_now_secs()));
+            match fs::copy(path, &corrupt_path) {
+                Ok(_) => error!(
+                    path = %corrupt_path.display(),
+                    "kept unreadable transmissions file"
+                ),
+                Err(error) => error!(
+                    path = %corrupt_path.display(),
+                    %error,
+                    "failed to keep unreadable transmissions file"
+                ),
+            }
+        }
+
+        let mut state = read_json_file(&backup_path).map_err(|backup_error| {
+            io::Error::new(
+                backup_error.kind(),
+                format!(
+                    "backup {} is unusable too: {backup_error}",
+                    backup_path.display()
+                ),
+            )
+        })?;
+        warn!(
+            count = state.entries.len(),
+            path = %backup_path.display(),
+            "recovered transmissions from backup"
+        );
+        state.entries.truncate(limit);
+        Ok(Some(state))
+    }
+
+    fn save(&self, state: &TransmissionState) -> io::Result<()> {
+        let result = self.write_file(state);
+        self.last_save.record(&result);
+        result
+    }
+
+    fn delete(&self, timestamp: u64) -> io::Result<bool> {
+        let Some(mut state) = self.load(usize::MAX)? else {
+            return Ok(false);
+        };
+        let before = state.entries.len();
+        state.entries.retain(|entry| entry.timestamp != timestamp);
+        if state.entries.len() == before {
+            return Ok(false);
+        }
+        state.modified_at = unix_now_secs();
+        self.save(&state)?;
+        Ok(true)
+    }
+
+    fn count(&self) -> io::Result<usize> {
+        Ok(self.archived_entries()?.len())
+    }
+
+    fn list(&self, offset: usize, limit: usize) -> io::Result<Vec<TransmissionEntry>> {
+        Ok(self
+            .archived_entries()?
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .collect())
+    }
+
+    fn after(&self, timestamp: u64, limit: usize) -> io::Result<Vec<TransmissionEntry>> {
+        Ok(self
+            .archived_entries()?
+            .into_iter()
+            .rev()
+            .filter(|entry| entry.timestamp > timestamp)
+            .take(limit)
+            .collect())
+    }
+
+    fn get(&self, timestamp: u64) -> io::Result<Option<TransmissionEntry>> {
+        Ok(self
+            .archived_entries()?
+            .into_iter()
+            .find(|entry| entry.timestamp == timestamp))
+    }
+
+    fn adjacent(&self, timestamp: u64) -> io::Result<(Option<u64>, Option<u64>)> {
+        let entries = self.archived_entries()?;
+        let older = entries
+            .iter()
+            .map(|entry| entry.timestamp)
+            .filter(|candidate| *candidate < timestamp)
+            .max();
+        let newer = entries
+            .iter()
+            .map(|entry| entry.timestamp)
+            .filter(|candidate| *candidate > timestamp)
+            .min();
+        Ok((older, newer))
+    }
+
+    fn check_writable(&self) -> io::Result<()> {
+        self.last_save.check()?;
+        let directory = self
+            .path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        if fs::metadata(directory)?.permissions().readonly() {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("{} is read-only", directory.display()),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The outcome of the most recent save, so readiness checks can report a
+/// failing disk without writing to it themselves.
+#[derive(Default)]
+struct LastSave(Mutex<Option<String>>);
+
+impl LastSave {
+    fn record(&self, result: &io::Result<()>) {
+        if let Ok(mut last_error) = self.0.lock() {
+            *last_error = result.as_ref().err().map(ToString::to_string);
+        }
+    }
+
+    fn check(&self) -> io::Result<()> {
+        match self.0.lock().as_deref() {
+            Ok(Some(error)) => Err(io::Error::other(format!("last save failed: {error}"))),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn read_json_file(path: &Path) -> io::Result<TransmissionState> {
+    let content = fs::read_to_string(path)?;
+    // An empty log is valid: the admin API can delete every entry.
+    serde_json::from_str(&content).map_err(io::Error::other)
+}
+
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Embedded SQLite storage that keeps every transmission ever recorded.
+pub(crate) struct SqliteStore {
+    connection: Mutex<Connection>,
+    last_save: LastSave,
+}
+
+const LAST_GENERATED_AT_KEY: &str = "last_generated_at";
+const SEED_KEY: &str = "seed";
+const RNG_STATE_KEY: &str = "rng_state";
+const LAST_SEEN_VERSION_KEY: &str = "last_seen_version";
+const MODIFIED_AT_KEY: &str = "modified_at";
+const JSON_MIGRATED_KEY: &str = "json_migrated";
+
+impl SqliteStore {
+    /// Opens (or creates) the database at `path`. The first time a database
+    /// is opened, any history in `legacy` is imported into it.
+    pub(crate) fn open(path: &Path, legacy: &JsonFileStore) -> io::Result<Self> {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent)?;
+        }
+
+        let connection = Connection::open(path).map_err(io::Error::other)?;
+        connection
+            .execute_batch(
+                "PRAGMA journal_mode = WAL;
+                 PRAGMA synchronous = FULL;
+                 CREATE TABLE IF NOT EXISTS transmissions (
+                     timestamp INTEGER PRIMARY KEY,
+                     message TEXT NOT NULL,
+                     kind TEXT NOT NULL DEFAULT 'generated',
+                     link TEXT
+                 );
+                 CREATE TABLE IF NOT EXISTS metadata (
+                     key TEXT PRIMARY KEY,
+                     value TEXT NOT NULL
+                 );",
+            )
+            .map_err(io::Error::other)?;
+        migrate_schema(&connection)?;
+
+        let store = Self {
+            connection: Mutex::new(connection),
+            last_save: LastSave::default(),
+        };
+        store.migrate_from_json(legacy, path)?;
+        Ok(store)
+    }
+
+    fn migrate_from_json(&self, legacy: &JsonFileStore, path: &Path) -> io::Result<()> {
+        if self.metadata(JSON_MIGRATED_KEY)?.is_some() {
+            return Ok(());
+        }
+
+        // A legacy file that cannot be read would otherwise stop every start.
+        // `load` has already kept a copy of it, and the file itself is left in
+        // place, so it can still be recovered by hand.
+        match legacy.load(usize::MAX) {
+            Ok(Some(state)) => {
+                self.save(&state)?;
+                info!(
+                    count = state.entries.len(),
+                    from = %legacy.path.display(),
+                    to = %path.display(),
+                    "migrated transmissions into SQLite"
+                );
+            }
+            Ok(None) => {}
+            Err(error) => error!(
+                from = %legacy.path.display(),
+                %error,
+                "skipped migrating unreadable transmissions into SQLite"
+            ),
+        }
+
+        self.set_metadata(JSON_MIGRATED_KEY, &unix_now_secs().to_string())
+    }
+
+    fn metadata(&self, key: &str) -> io::Result<Option<String>> {
+        let connection = self.lock()?;
+        connection
+            .query_row(
+                "SELECT value FROM metadata WHERE key = ?1",
+                params![key],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(io::Error::other)
+    }
+
+    fn set_metadata(&self, key: &str, value: &str) -> io::Result<()> {
+        let connection = self.lock()?;
+        connection
+            .execute(
+                "INSERT INTO metadata (key, value) VALUES (?1, ?2)
+                 ON CONFLICT (key) DO UPDATE SET value = excluded.value",
+                params![key, value],
+            )
+            .map_err(io::Error::other)?;
+        Ok(())
+    }
+
+    fn write_state(&self, state: &TransmissionState) -> io::Result<()> {
+        let mut connection = self.lock()?;
+        let transaction = connection.transaction().map_err(io::Error::other)?;
+        for entry in &state.entries {
+            transaction
+                .execute(
+                    "INSERT INTO transmissions (timestamp, message, kind, link)
+                     VALUES (?1, ?2, ?3, ?4)
+                     ON CONFLICT (timestamp) DO UPDATE SET
+                         message = excluded.message,
+                         kind = excluded.kind,
+                         link = excluded.link",
+                    params![
+                        entry.timestamp,
+                        entry.message,
+                        entry.kind.as_str(),
+                        entry.link
+                    ],
+                )
+                .map_err(io::Error::other)?;
+        }
+        let mut metadata = vec![
+            (LAST_GENERATED_AT_KEY, state.last_generated_at.to_string()),
+            (RNG_STATE_KEY, state.rng_state.to_string()),
+            (MODIFIED_AT_KEY, state.modified_at.to_string()),
+        ];
+        metadata.extend(state.seed.map(|seed| (SEED_KEY, seed.to_string())));
+        metadata.extend(
+            state
+                .last_seen_version
+                .clone()
+                .map(|version| (LAST_SEEN_VERSION_KEY, version)),
+        );
+        for (key, value) in metadata {
+            transaction
+                .execute(
+                    "INSERT INTO metadata (key, value) VALUES (?1, ?2)
+                     ON CONFLICT (key) DO UPDATE SET value = excluded.value",
+                    params![key, value],
+                )
+                .map_err(io::Error::other)?;
+        }
+        transaction.commit().map_err(io::Error::other)
+    }
+
+    fn lock(&self) -> io::Result<std::sync::MutexGuard<'_, Connection>> {
+        self.connection
+            .lock()
+            .map_err(|_| io::Error::other("transmission store lock poisoned"))
+    }
+}
+
+impl TransmissionStore for SqliteStore {
+    fn load(&self, limit: usize) -> io::Result<Option<TransmissionState>> {
+        let entries = {
+            let connection = self.lock()?;
+            let mut statement = connection
+                .prepare(
+                    "SELECT timestamp, message, kind, link FROM transmissions
+                     ORDER BY timestamp DESC LIMIT ?1",
+                )
+                .map_err(io::Error::other)?;
+            statement
+                .query_map(
+                    params![i64::try_from(limit).unwrap_or(i64::MAX)],
+                    entry_from_row,
+                )
+                .and_then(Iterator::collect::<Result<Vec<_>, _>>)
+                .map_err(io::Error::other)?
+        };
+
+        // Every save records `last_generated_at`, so a log whose entries were
+        // all deleted still loads, keeping its seed and announced version.
+        let last_generated_at = match (self.metadata(LAST_GENERATED_AT_KEY)?, entries.first()) {
+            (Some(value), _) => value.parse().map_err(io::Error::other)?,
+            (None, Some(newest)) => newest.timestamp,
+            (None, None) => return Ok(None),
+        };
+        let seed = match self.metadata(SEED_KEY)? {
+            Some(value) => Some(value.parse().map_err(io::Error::other)?),
+            None => None,
+        };
+        let rng_state = match self.metadata(RNG_STATE_KEY)? {
+            Some(value) => value.parse().map_err(io::Error::other)?,
+            None => 0,
+        };
+        let last_seen_version = self.metadata(LAST_SEEN_VERSION_KEY)?;
+        let modified_at = match self.metadata(MODIFIED_AT_KEY)? {
+            Some(value) => value.parse().map_err(io::Error::other)?,
+            None => 0,
+        };
+
+        Ok(Some(TransmissionState {
+            last_generated_at,
+            seed,
+            rng_state,
+            last_seen_version,
+            modified_at,
+            entries,
+        }))
+    }
+
+    fn save(&self, state: &TransmissionState) -> io::Result<()> {
+        let result = self.write_state(state);
+        self.last_save.record(&result);
+        result
+    }
+
+    fn delete(&self, timestamp: u64) -> io::Result<bool> {
+        let deleted = self
+            .lock()?
+            .execute(
+                "DELETE FROM transmissions WHERE timestamp = ?1",
+                params![timestamp],
+            )
+            .map_err(io::Error::other)?
+            > 0;
+        if deleted {
+            self.set_metadata(MODIFIED_AT_KEY, &unix_now_secs().to_string())?;
+        }
+        Ok(deleted)
+    }
+
+    fn count(&self) -> io::Result<usize> {
+        let connection = self.lock()?;
+        connection
+            .query_row("SELECT COUNT(*) FROM transmissions", [], |row| row.get(0))
+            .map_err(io::Error::other)
+    }
+
+    fn list(&self, offset: usize, limit: usize) -> io::Result<Vec<TransmissionEntry>> {
+        let connection = self.lock()?;
+        let mut statement = connection
+            .prepare(
+                "SELECT timestamp, message, kind, link FROM transmissions
+                 ORDER BY timestamp DESC LIMIT ?1 OFFSET ?2",
+            )
+            .map_err(io::Error::other)?;
+        statement
+            .query_map(params![limit, offset], entry_from_row)
+            .and_then(Iterator::collect::<Result<Vec<_>, _>>)
+            .map_err(io::Error::other)
+    }
+
+    fn after(&self, timestamp: u64, limit: usize) -> io::Result<Vec<TransmissionEntry>> {
+        let connection = self.lock()?;
+        let mut statement = connection
+            .prepare(
+                "SELECT timestamp, message, kind, link FROM transmissions
+                 WHERE timestamp > ?1 ORDER BY timestamp ASC LIMIT ?2",
+            )
+            .map_err(io::Error::other)?;
+        statement
+            .query_map(params![timestamp, limit], entry_from_row)
+            .and_then(Iterator::collect::<Result<Vec<_>, _>>)
+            .map_err(io::Error::other)
+    }
+
+    fn get(&self, timestamp: u64) -> io::Result<Option<TransmissionEntry>> {
+        let connection = self.lock()?;
+        connection
+            .query_row(
+                "SELECT timestamp, message, kind, link FROM transmissions WHERE timestamp = ?1",
+                params![timestamp],
+                entry_from_row,
+            )
+            .optional()
+            .map_err(io::Error::other)
+    }
+
+    fn adjacent(&self, timestamp: u64) -> io::Result<(Option<u64>, Option<u64>)> {
+        let connection = self.lock()?;
+        connection
+            .query_row(
+                "SELECT
+                     (SELECT MAX(timestamp) FROM transmissions WHERE timestamp < ?1),
+                     (SELECT MIN(timestamp) FROM transmissions WHERE timestamp > ?1)",
+                params![timestamp],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .map_err(io::Error::other)
+    }
+
+    fn check_writable(&self) -> io::Result<()> {
+        self.last_save.check()?;
+        let connection = self.lock()?;
+        if connection
+            .is_readonly(rusqlite::MAIN_DB)
+            .map_err(io::Error::other)?
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "database is read-only",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Brings databases created by older versions up to the current schema.
+fn migrate_schema(connection: &Connection) -> io::Result<()> {
+    let columns = connection
+        .prepare("SELECT name FROM pragma_table_info('transmissions')")
+        .and_then(|mut statement| {
+            statement
+                .query_map([], |row| row.get::<_, String>(0))?
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(io::Error::other)?;
+
+    for (column, definition) in [
+        ("kind", "kind TEXT NOT NULL DEFAULT 'generated'"),
+        ("link", "link TEXT"),
+    ] {
+        if !columns.iter().any(|name| name == column) {
+            connection
+                .execute(
+                    &format!("ALTER TABLE transmissions ADD COLUMN {definition}"),
+                    [],
+                )
+                .map_err(io::Error::other)?;
+        }
+    }
+
+    // Time labels are derived when rendering now that the display timezone
+    // is configurable.
+    if columns.iter().any(|name| name == "time_label") {
+        connection
+            .execute("ALTER TABLE transmissions DROP COLUMN time_label", [])
+            .map_err(io::Error::other)?;
+    }
+
+    Ok(())
+}
+
+fn entry_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<TransmissionEntry> {
+    let kind: String = row.get(2)?;
+    Ok(TransmissionEntry {
+        timestamp: row.get(0)?,
+        message: row.get(1)?,
+        kind: TransmissionKind::parse(&kind).unwrap_or_default(),
+        link: row.get(3)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A scratch directory below the system temporary directory, removed
+    /// again when dropped.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path =
+                std::env::temp_dir().join(format!("gpr-store-{}-{name}", std::process::id()));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+
+        fn join(&self, name: &str) -> PathBuf {
+            self.0.join(name)
+        }
+
+        fn files_starting_with(&self, prefix: &str) -> Vec<PathBuf> {
+            fs::read_dir(&self.0)
+                .unwrap()
+                .map(|entry| entry.unwrap().path())
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.starts_with(prefix))
+                })
+                .collect()
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn state(timestamps: &[u64]) -> TransmissionState {
+        TransmissionState {
+            last_generated_at: 100,
+            seed: Some(7),
+            rng_state: 9,
+            last_seen_version: Some("1.0.0".to_string()),
+            modified_at: 100,
+            entries: timestamps
+                .iter()
+                .map(|&timestamp| TransmissionEntry {
+                    timestamp,
+                    message: format!("message {timestamp}"),
+                    kind: TransmissionKind::Generated,
+                    link: None,
+                })
+                .collect(),
+        }
+    }
+
+    fn timestamps(state: &TransmissionState) -> Vec<u64> {
+        state.entries.iter().map(|entry| entry.timestamp).collect()
+    }
+
+    #[test]
+    fn json_saves_keep_the_previous_file_as_a_backup() {
+        let dir = TempDir::new("json-backup");
+        let path = dir.join("transmissions.json");
+        let store = JsonFileStore::new(path.clone());
+
+        assert!(store.load(usize::MAX).unwrap().is_none());
+        store.save(&state(&[3, 2, 1])).unwrap();
+        store.save(&state(&[4, 3, 2, 1])).unwrap();
+
+        let loaded = store.load(2).unwrap().unwrap();
+        assert_eq!(timestamps(&loaded), [4, 3]);
+        assert_eq!(loaded.seed, Some(7));
+        let backup = read_json_file(&sibling_path(&path, ".bak")).unwrap();
+        assert_eq!(timestamps(&backup), [3, 2, 1]);
+    }
+
+    #[test]
+    fn corrupt_json_falls_back_to_the_backup_and_is_kept() {
+        let dir = TempDir::new("json-corrupt");
+        let path = dir.join("transmissions.json");
+        let store = JsonFileStore::new(path.clone());
+        store.save(&state(&[1])).unwrap();
+        store.save(&state(&[2, 1])).unwrap();
+        fs::write(&path, "{ not json").unwrap();
+
+        let loaded = store.load(usize::MAX).unwrap().unwrap();
+        assert_eq!(timestamps(&loaded), [1]);
+        let kept = dir.files_starting_with("transmissions.json.corrupt-");
+        assert_eq!(kept.len(), 1);
+        assert_eq!(fs::read_to_string(&kept[0]).unwrap(), "{ not json");
+    }
+
+    #[test]
+    fn corrupt_json_without_a_backup_is_an_error() {
+        let dir = TempDir::new("json-corrupt-no-backup");
+        let path = dir.join("transmissions.json");
+        fs::write(&path, "{ not json").unwrap();
+
+        assert!(JsonFileStore::new(path).load(usize::MAX).is_err());
+        assert_eq!(
+            dir.files_starting_with("transmissions.json.corrupt-").len(),
+            1
+        );
+    }
+
+    #[test]
+    fn an_emptied_json_log_still_loads() {
+        let dir = TempDir::new("json-empty");
+        let store = JsonFileStore::new(dir.join("transmissions.json"));
+        store.save(&state(&[1])).unwrap();
+
+        assert!(store.delete(1).unwrap());
+        let loaded = store.load(usize::MAX).unwrap().unwrap();
+        assert!(loaded.entries.is_empty());
+        assert_eq!(loaded.seed, Some(7));
+        assert!(loaded.modified_at > 100);
+    }
+
+    #[test]
+    fn concurrent_json_saves_leave_a_complete_file() {
+        let dir = TempDir::new("json-concurrent");
+        let path = dir.join("transmissions.json");
+        let store = Arc::new(JsonFileStore::new(path.clone()));
+
+        let writers: Vec<_> = (0..8u64)
+            .map(|writer| {
+                let store = store.clone();
+                std::thread::spawn(move || {
+                    for round in 0..20 {
+                        store.save(&state(&[writer * 100 + round, writer])).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        assert_eq!(read_json_file(&path).unwrap().entries.len(), 2);
+        assert!(!sibling_path(&path, ".tmp").exists());
+    }
+
+    #[test]
+    fn sqlite_imports_the_json_log_once() {
+        let dir = TempDir::new("sqlite-import");
+        let legacy = JsonFileStore::new(dir.join("transmissions.json"));
+        legacy.save(&state(&[2, 1])).unwrap();
+
+        let store = SqliteStore::open(&dir.join("transmissions.sqlite3"), &legacy).unwrap();
+        let loaded = store.load(usize::MAX).unwrap().unwrap();
+        assert_eq!(timestamps(&loaded), [2, 1]);
+        assert_eq!(loaded.seed, Some(7));
+        assert_eq!(loaded.last_seen_version.as_deref(), Some("1.0.0"));
+        drop(store);
+
+        legacy.save(&state(&[5])).unwrap();
+        let store = SqliteStore::open(&dir.join("transmissions.sqlite3"), &legacy).unwrap();
+        assert_eq!(
+            timestamps(&store.load(usize::MAX).unwrap().unwrap()),
+            [2, 1]
+        );
+    }
+
+    #[test]
+    fn sqlite_starts_empty_when_the_json_log_is_unreadable() {
+        let dir = TempDir::new("sqlite-corrupt-import");
+        let legacy_path = dir.join("transmissions.json");
+        fs::write(&legacy_path, "{ not json").unwrap();
+        let legacy = JsonFileStore::new(legacy_path.clone());
+
+        let store = SqliteStore::open(&dir.join("transmissions.sqlite3"), &legacy).unwrap();
+        assert!(store.load(usize::MAX).unwrap().is_none());
+        assert_eq!(fs::read_to_string(&legacy_path).unwrap(), "{ not json");
+        assert_eq!(
+            dir.files_starting_with("transmissions.json.corrupt-").len(),
+            1
+        );
+        assert!(store.metadata(JSON_MIGRATED_KEY).unwrap().is_some());
+    }
+
+    #[test]
+    fn an_emptied_sqlite_log_keeps_its_metadata() {
+        let dir = TempDir::new("sqlite-empty");
+        let legacy = JsonFileStore::new(dir.join("transmissions.json"));
+        let store = SqliteStore::open(&dir.join("transmissions.sqlite3"), &legacy).unwrap();
+        store.save(&state(&[2, 1])).unwrap();
+
+        assert!(store.delete(2).unwrap());
+        assert!(store.delete(1).unwrap());
+        assert!(!store.delete(1).unwrap());
+        let loaded = store.load(usize::MAX).unwrap().unwrap();
+        assert!(loaded.entries.is_empty());
+        assert_eq!(loaded.seed, Some(7));
+        assert_eq!(loaded.rng_state, 9);
+        assert_eq!(loaded.last_seen_version.as_deref(), Some("1.0.0"));
+        assert!(loaded.modified_at > 100);
+    }
+
+    #[test]
+    fn sqlite_upgrades_the_original_schema() {
+        let dir = TempDir::new("sqlite-schema");
+        let path = dir.join("transmissions.sqlite3");
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE transmissions (
+                     timestamp INTEGER PRIMARY KEY,
+                     time_label TEXT NOT NULL,
+                     message TEXT NOT NULL
+                 );
+                 INSERT INTO transmissions VALUES (1, '00:00:01', 'old');",
+            )
+            .unwrap();
+
+        let legacy = JsonFileStore::new(dir.join("transmissions.json"));
+        let store = SqliteStore::open(&path, &legacy).unwrap();
+        let entry = store.get(1).unwrap().unwrap();
+        assert_eq!(entry.message, "old");
+        assert_eq!(entry.kind, TransmissionKind::Generated);
+        assert_eq!(entry.link, None);
+
+        let mut manual = state(&[2]);
+        manual.entries[0].kind = TransmissionKind::Manual;
+        manual.entries[0].link = Some("/transmissions".to_string());
+        store.save(&manual).unwrap();
+        let saved = store.get(2).unwrap().unwrap();
+        assert_eq!(saved.kind, TransmissionKind::Manual);
+        assert_eq!(saved.link.as_deref(), Some("/transmissions"));
+    }
+}
@@ -0,0 +1,184 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+/// A `MAJOR.MINOR.PATCH[-PRERELEASE][+BUILD]` version ordered by semver
+/// precedence. Missing minor or patch components are treated as zero.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct SemanticVersion {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    prerelease: Vec<String>,
+}
+
+impl SemanticVersion {
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let value = value
+            .strip_prefix('v')
+            .or_else(|| value.strip_prefix('V'))
+            .unwrap_or(value);
+        let value = value.split_once('+').map_or(value, |(version, _)| version);
+        let (core, prerelease) = match value.split_once('-') {
+            Some((core, prerelease)) => (core, Some(prerelease)),
+            None => (value, None),
+        };
+
+        let mut numbers = core.split('.');
+        let major = parse_number(numbers.next()?)?;
+        let minor = numbers.next().map_or(Some(0), parse_number)?;
+        let patch = numbers.next().map_or(Some(0), parse_number)?;
+        if numbers.next().is_some() {
+            return None;
+        }
+
+        let prerelease = match prerelease {
+            Some(prerelease) => {
+                let identifiers: Vec<String> = prerelease.split('.').map(str::to_string).collect();
+                if identifiers.iter().any(|identifier| identifier.is_empty()) {
+                    return None;
+                }
+                identifiers
+            }
+            None => Vec::new(),
+        };
+
+        Some(Self {
+            major,
+            minor,
+            patch,
+            prerelease,
+        })
+    }
+}
+
+impl fmt::Display for SemanticVersion {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.prerelease.is_empty() {
+            write!(formatter, "-{}", self.prerelease.join("."))?;
+        }
+        Ok(())
+    }
+}
+
+impl Ord for SemanticVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(
+                || match (self.prerelease.is_empty(), other.prerelease.is_empty()) {
+                    (true, true) => Ordering::Equal,
+                    (true, false) => Ordering::Greater,
+                    (false, true) => Ordering::Less,
+                    (false, false) => compare_prerelease(&self.prerelease, &other.prerelease),
+                },
+            )
+    }
+}
+
+impl PartialOrd for SemanticVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn parse_number(value: &str) -> Option<u64> {
+    if value.is_empty() || !value.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+
+    value.parse().ok()
+}
+
+fn compare_prerelease(left: &[String], right: &[String]) -> Ordering {
+    for (left, right) in left.iter().zip(right) {
+        let ordering = match (parse_number(left), parse_number(right)) {
+            (Some(left), Some(right)) => left.cmp(&right),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => left.cmp(right),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    left.len().cmp(&right.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(value: &str) -> SemanticVersion {
+        SemanticVersion::parse(value).unwrap_or_else(|| panic!("{value} should parse"))
+    }
+
+    fn assert_ascending(values: &[&str]) {
+        for pair in values.windows(2) {
+            assert!(
+                version(pair[0]) < version(pair[1]),
+                "{} < {}",
+                pair[0],
+                pair[1]
+            );
+        }
+    }
+
+    #[test]
+    fn follows_the_semver_precedence_example() {
+        assert_ascending(&[
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+        ]);
+    }
+
+    #[test]
+    fn prereleases_sort_before_their_release_but_after_earlier_ones() {
+        assert_ascending(&["0.9.9", "1.0.0-rc.1", "1.0.0", "1.0.1-alpha", "1.0.1"]);
+    }
+
+    #[test]
+    fn numeric_identifiers_compare_numerically_and_before_alphanumeric_ones() {
+        assert_ascending(&["1.0.0-2", "1.0.0-10", "1.0.0-1a", "1.0.0-a"]);
+        assert_ascending(&["1.9.0", "1.10.0", "10.0.0"]);
+    }
+
+    #[test]
+    fn missing_minor_and_patch_are_zero() {
+        assert_eq!(version("1"), version("1.0.0"));
+        assert_eq!(version("1.2"), version("1.2.0"));
+        assert_eq!(version("v1.2-beta").to_string(), "1.2.0-beta");
+        assert_ascending(&["1", "1.0.1", "1.1"]);
+    }
+
+    #[test]
+    fn build_metadata_and_prefix_are_ignored() {
+        assert_eq!(version("V1.2.3+build.5"), version("1.2.3"));
+        assert_eq!(version(" v1.2.3-rc.1+sha "), version("1.2.3-rc.1"));
+    }
+
+    #[test]
+    fn rejects_malformed_versions() {
+        for value in [
+            "",
+            "v",
+            "1.",
+            "1..2",
+            "1.2.3.4",
+            "1.x",
+            "-1.0",
+            "1.0.0-",
+            "1.0.0-a..b",
+        ] {
+            assert_eq!(SemanticVersion::parse(value), None, "{value}");
+        }
+    }
+}